@@ -0,0 +1,66 @@
+//! ASCII portraits of each species for `--art`, embedded from `data/art/`.
+
+use animal_age::animal::Animal;
+
+const DOG: &str = include_str!("../data/art/dog.txt");
+const CAT: &str = include_str!("../data/art/cat.txt");
+const HORSE: &str = include_str!("../data/art/horse.txt");
+const PIG: &str = include_str!("../data/art/pig.txt");
+const PARAKEET: &str = include_str!("../data/art/parakeet.txt");
+const SNAKE: &str = include_str!("../data/art/snake.txt");
+const GOLDFISH: &str = include_str!("../data/art/goldfish.txt");
+const RABBIT: &str = include_str!("../data/art/rabbit.txt");
+const HAMSTER: &str = include_str!("../data/art/hamster.txt");
+const GUINEA_PIG: &str = include_str!("../data/art/guinea_pig.txt");
+const FERRET: &str = include_str!("../data/art/ferret.txt");
+const TURTLE: &str = include_str!("../data/art/turtle.txt");
+const COW: &str = include_str!("../data/art/cow.txt");
+const GOAT: &str = include_str!("../data/art/goat.txt");
+const SHEEP: &str = include_str!("../data/art/sheep.txt");
+const CHICKEN: &str = include_str!("../data/art/chicken.txt");
+const MOUSE: &str = include_str!("../data/art/mouse.txt");
+const CHINCHILLA: &str = include_str!("../data/art/chinchilla.txt");
+const MACAW: &str = include_str!("../data/art/macaw.txt");
+const LIZARD: &str = include_str!("../data/art/lizard.txt");
+const LLAMA: &str = include_str!("../data/art/llama.txt");
+
+/// The species' portrait, a few lines of plain ASCII ending in a newline.
+pub fn portrait(animal: Animal) -> &'static str {
+    match animal {
+        Animal::SmallDog | Animal::MediumDog | Animal::BigDog | Animal::GiantDog => DOG,
+        Animal::Cat => CAT,
+        Animal::Horse | Animal::Pony | Animal::Donkey | Animal::Mule => HORSE,
+        Animal::Pig => PIG,
+        Animal::Parakeet | Animal::Cockatiel | Animal::Canary => PARAKEET,
+        Animal::Snake => SNAKE,
+        Animal::Goldfish | Animal::Koi | Animal::Betta => GOLDFISH,
+        Animal::Rabbit => RABBIT,
+        Animal::Hamster => HAMSTER,
+        Animal::GuineaPig => GUINEA_PIG,
+        Animal::Ferret => FERRET,
+        Animal::Turtle | Animal::Tortoise => TURTLE,
+        Animal::Cow => COW,
+        Animal::Goat => GOAT,
+        Animal::Sheep => SHEEP,
+        Animal::Chicken => CHICKEN,
+        Animal::Rat | Animal::Mouse | Animal::Gerbil => MOUSE,
+        Animal::Chinchilla => CHINCHILLA,
+        Animal::Macaw => MACAW,
+        Animal::BeardedDragon | Animal::LeopardGecko | Animal::Iguana => LIZARD,
+        Animal::Llama | Animal::Alpaca => LLAMA,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portraits_are_small_ascii() {
+        for animal in Animal::ALL {
+            let art = portrait(animal);
+            assert!(art.is_ascii() && art.ends_with('\n'), "{}", animal.key());
+            assert!(art.lines().count() <= 8, "{}", animal.key());
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Text progress bars for the terminal output and the comparison table.
+
+use crate::theme::{Paint, Theme};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Partial cells of the `blocks` style, from one to seven eighths filled.
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const FULL_BLOCK: char = '█';
+/// Cells of the range an estimate likely falls in, for each style.
+const ASCII_SHADE: char = '-';
+const LIGHT_SHADE: char = '░';
+
+/// Marker of the animal's position on an `--inline` bar, in magenta.
+pub const ANIMAL_MARK: (char, Paint) = ('A', Paint::Ansi(5));
+/// Marker of the human-equivalent position on an `--inline` bar, in green.
+pub const HUMAN_MARK: (char, Paint) = ('H', Paint::Ansi(2));
+
+/// `mark`'s character in its color, when color is on.
+pub fn paint_mark((c, paint): (char, Paint), color: bool) -> String {
+    match paint.escape().filter(|_| color) {
+        Some(escape) => format!("{}{}\x1b[0m", escape, c),
+        None => c.to_string(),
+    }
+}
+
+/// Whether the terminal advertises 24-bit color through `COLORTERM`.
+pub fn truecolor() -> bool {
+    std::env::var("COLORTERM").is_ok_and(|value| value == "truecolor" || value == "24bit")
+}
+
+/// The gradient color at `t` (0 at the start of the bar, 1 at the end), blending
+/// from the first stop to the second halfway along, then on to the third.
+fn gradient_color(t: f32, stops: [[u8; 3]; 3]) -> [u8; 3] {
+    let t = t.clamp(0.0, 1.0) * 2.0;
+    let (from, to, share) = if t <= 1.0 {
+        (stops[0], stops[1], t)
+    } else {
+        (stops[1], stops[2], t - 1.0)
+    };
+    let mix = |i: usize| (from[i] as f32 + (to[i] as f32 - from[i] as f32) * share) as u8;
+    [mix(0), mix(1), mix(2)]
+}
+
+/// `cells` colored one by one along the gradient, as the start of a bar `width`
+/// characters wide.
+fn paint_gradient(cells: &str, width: usize, stops: [[u8; 3]; 3]) -> String {
+    let last = width.saturating_sub(1).max(1) as f32;
+    let mut bar = String::new();
+    for (i, c) in cells.chars().enumerate() {
+        let [r, g, b] = gradient_color(i as f32 / last, stops);
+        bar.push_str(&format!("\x1b[38;2;{};{};{}m{}", r, g, b, c));
+    }
+    if !bar.is_empty() {
+        bar.push_str("\x1b[0m");
+    }
+    bar
+}
+
+/// The annotation after a bar's percentage: roughly how many of the typical
+/// lifespan's years are left.
+pub fn years_left(years: f32) -> String {
+    if years >= 1.5 {
+        format!("~{:.0} yrs left", years)
+    } else if years >= 0.5 {
+        "~1 yr left".to_string()
+    } else if years > 0.0 {
+        "<1 yr left".to_string()
+    } else {
+        "past typical lifespan".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum BarStyle {
+    /// One `=` per filled cell
+    #[default]
+    Ascii,
+    /// Unicode block elements, filling cells an eighth at a time
+    Blocks,
+}
+
+/// How full a bar is, which picks its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Low,
+    Mid,
+    High,
+}
+
+/// Percentages of a lifespan at which bars turn from low to mid (yellow) and
+/// from mid to high (red).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "[f32; 2]", into = "[f32; 2]")]
+pub struct Thresholds {
+    pub mid: f32,
+    pub high: f32,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            mid: 60.0,
+            high: 80.0,
+        }
+    }
+}
+
+impl Thresholds {
+    /// The level of a bar filled to `progress` (a fraction, not a percentage).
+    pub fn level(&self, progress: f32) -> Level {
+        let pct = progress * 100.0;
+        if pct >= self.high {
+            Level::High
+        } else if pct >= self.mid {
+            Level::Mid
+        } else {
+            Level::Low
+        }
+    }
+}
+
+impl TryFrom<[f32; 2]> for Thresholds {
+    type Error = String;
+
+    fn try_from([mid, high]: [f32; 2]) -> Result<Self, Self::Error> {
+        if !(0.0..=100.0).contains(&mid) || !(0.0..=100.0).contains(&high) {
+            return Err("thresholds must be percentages between 0 and 100".to_string());
+        }
+        if mid > high {
+            return Err(format!(
+                "the yellow threshold ({}) must not exceed the red one ({})",
+                mid, high
+            ));
+        }
+        Ok(Thresholds { mid, high })
+    }
+}
+
+impl From<Thresholds> for [f32; 2] {
+    fn from(thresholds: Thresholds) -> Self {
+        [thresholds.mid, thresholds.high]
+    }
+}
+
+impl FromStr for Thresholds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |part: &str| {
+            part.trim()
+                .trim_end_matches('%')
+                .parse::<f32>()
+                .map_err(|_| format!("'{}' is not a percentage", part.trim()))
+        };
+        let (mid, high) = s
+            .split_once(',')
+            .ok_or_else(|| "expected two percentages, like 60,80".to_string())?;
+        Thresholds::try_from([parse(mid)?, parse(high)?])
+    }
+}
+
+/// How progress bars are drawn.
+#[derive(Debug, Clone, Copy)]
+pub struct Bars {
+    pub style: BarStyle,
+    /// Replaces the style's character for a filled cell.
+    pub fill: Option<char>,
+    /// Character for an empty cell.
+    pub empty: char,
+    /// Bar width in characters, overriding the one fitted to the terminal.
+    pub width: Option<usize>,
+    /// Color bars with a 24-bit gradient instead of one threshold color.
+    pub gradient: bool,
+    /// Bar colors, label style, and percentage format.
+    pub theme: Theme,
+    /// Where the threshold colors change.
+    pub thresholds: Thresholds,
+    /// Draw the animal and its human equivalent as two markers on one bar.
+    pub inline: bool,
+    /// Decimal places of human years next to the bars.
+    pub decimals: usize,
+}
+
+impl Default for Bars {
+    fn default() -> Self {
+        Bars {
+            style: BarStyle::default(),
+            fill: None,
+            empty: ' ',
+            width: None,
+            gradient: false,
+            theme: Theme::default(),
+            thresholds: Thresholds::default(),
+            inline: false,
+            decimals: 1,
+        }
+    }
+}
+
+impl Bars {
+    /// A bar `width` characters wide, filled to `progress` (clamped to 0..=1).
+    pub fn render(&self, progress: f32, width: usize) -> String {
+        let filled = self.filled(progress, width);
+        let padding = width - filled.chars().count();
+        filled + &self.empty.to_string().repeat(padding)
+    }
+
+    /// Like [`Bars::render`], with each filled cell colored by its position along
+    /// a gradient through the theme's low, mid, and high colors.
+    pub fn render_gradient(&self, progress: f32, width: usize, stops: [[u8; 3]; 3]) -> String {
+        let filled = self.filled(progress, width);
+        let padding = width - filled.chars().count();
+        paint_gradient(&filled, width, stops) + &self.empty.to_string().repeat(padding)
+    }
+
+    /// A bar `width` characters wide for an estimate: filled to `low`, then
+    /// lightly shaded on to `high` (both clamped to 0..=1), the range the
+    /// estimate likely falls in. Whole cells only, so the shading starts cleanly.
+    pub fn render_band(
+        &self,
+        low: f32,
+        high: f32,
+        width: usize,
+        stops: Option<[[u8; 3]; 3]>,
+    ) -> String {
+        let (fill, shade) = match self.style {
+            BarStyle::Ascii => (self.fill.unwrap_or('='), ASCII_SHADE),
+            BarStyle::Blocks => (self.fill.unwrap_or(FULL_BLOCK), LIGHT_SHADE),
+        };
+        let solid = (low.clamp(0.0, 1.0) * width as f32) as usize;
+        let shaded = ((high.clamp(0.0, 1.0) * width as f32).round() as usize).max(solid);
+        let body = fill.to_string().repeat(solid) + &shade.to_string().repeat(shaded - solid);
+        let body = match stops {
+            Some(stops) => paint_gradient(&body, width, stops),
+            None => body,
+        };
+        body + &self.empty.to_string().repeat(width - shaded)
+    }
+
+    /// A bar `width` characters wide for a lifespan whose typical length falls
+    /// short of the maximum: filled to `age` across the typical part, then a
+    /// bracketed stretch on to the maximum. `age` and `typical` are fractions of
+    /// the maximum; the stretch past the typical lifespan takes the last
+    /// gradient color.
+    pub fn render_range(
+        &self,
+        age: f32,
+        typical: f32,
+        width: usize,
+        stops: Option<[[u8; 3]; 3]>,
+    ) -> String {
+        let inner = width.saturating_sub(2);
+        let left = ((typical * inner as f32).round() as usize).min(inner);
+        let beyond = if typical < 1.0 {
+            (age - typical) / (1.0 - typical)
+        } else {
+            0.0
+        };
+        let segment = |progress: f32, width: usize, stops: Option<[[u8; 3]; 3]>| match stops {
+            Some(stops) => self.render_gradient(progress, width, stops),
+            None => self.render(progress, width),
+        };
+        format!(
+            "{}[{}]",
+            segment(age / typical, left, stops),
+            segment(beyond, inner - left, stops.map(|stops| [stops[2]; 3]))
+        )
+    }
+
+    /// A track `width` characters wide with the [`ANIMAL_MARK`] at `animal` and
+    /// the [`HUMAN_MARK`] at `human`, both fractions of their lifespans (clamped
+    /// to 0..=1). The animal's mark wins when the two share a cell.
+    pub fn render_inline(&self, animal: f32, human: f32, width: usize, color: bool) -> String {
+        let width = width.max(1);
+        let track = match self.style {
+            BarStyle::Ascii => '-',
+            BarStyle::Blocks => '─',
+        };
+        let column =
+            |progress: f32| (progress.clamp(0.0, 1.0) * (width - 1) as f32).round() as usize;
+        let (animal, human) = (column(animal), column(human));
+        (0..width)
+            .map(|i| match i {
+                _ if i == animal => paint_mark(ANIMAL_MARK, color),
+                _ if i == human => paint_mark(HUMAN_MARK, color),
+                _ => track.to_string(),
+            })
+            .collect()
+    }
+
+    /// The filled part of a bar `width` characters wide.
+    fn filled(&self, progress: f32, width: usize) -> String {
+        let progress = progress.clamp(0.0, 1.0);
+        match self.style {
+            BarStyle::Ascii => {
+                let fill = self.fill.unwrap_or('=');
+                fill.to_string().repeat((progress * width as f32) as usize)
+            }
+            BarStyle::Blocks => {
+                let eighths = (progress * width as f32 * 8.0) as usize;
+                let fill = self.fill.unwrap_or(FULL_BLOCK);
+                let mut bar = fill.to_string().repeat(eighths / 8);
+                let partial = eighths % 8;
+                if partial > 0 {
+                    bar.push(EIGHTHS[partial - 1]);
+                }
+                bar
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_resolve_small_differences() {
+        let ascii = Bars::default();
+        assert_eq!(ascii.render(0.5, 10), "=====     ");
+        assert_eq!(ascii.render(0.53, 10), ascii.render(0.5, 10));
+
+        let blocks = Bars {
+            style: BarStyle::Blocks,
+            ..Bars::default()
+        };
+        assert_eq!(blocks.render(0.5, 10), "█████     ");
+        assert_eq!(blocks.render(0.53, 10), "█████▎    ");
+        assert_eq!(blocks.render(1.5, 4), "████");
+    }
+
+    #[test]
+    fn test_custom_characters() {
+        let bars = Bars {
+            fill: Some('#'),
+            empty: '·',
+            ..Bars::default()
+        };
+        assert_eq!(bars.render(0.3, 10), "###·······");
+    }
+
+    #[test]
+    fn test_range_brackets_the_stretch_past_typical() {
+        let bars = Bars::default();
+        // Typical lifespan at 80% of the maximum, age at 60% of it.
+        assert_eq!(bars.render_range(0.6, 0.8, 12, None), "======  [  ]");
+        assert_eq!(bars.render_range(0.95, 0.8, 12, None), "========[= ]");
+        assert_eq!(bars.render_range(1.2, 0.8, 12, None), "========[==]");
+    }
+
+    #[test]
+    fn test_band_shades_the_likely_range() {
+        let ascii = Bars::default();
+        assert_eq!(ascii.render_band(0.3, 0.5, 10, None), "===--     ");
+        assert_eq!(ascii.render_band(0.9, 1.2, 10, None), "=========-");
+        let blocks = Bars {
+            style: BarStyle::Blocks,
+            ..Bars::default()
+        };
+        assert_eq!(blocks.render_band(0.2, 0.4, 5, None), "█░   ");
+    }
+
+    #[test]
+    fn test_inline_markers() {
+        let bars = Bars::default();
+        assert_eq!(bars.render_inline(0.2, 0.5, 11, false), "--A--H-----");
+        assert_eq!(bars.render_inline(1.3, 1.0, 5, false), "----A");
+        assert_eq!(
+            bars.render_inline(0.0, 1.0, 3, true),
+            "\x1b[35mA\x1b[0m-\x1b[32mH\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_years_left() {
+        assert_eq!(years_left(11.6), "~12 yrs left");
+        assert_eq!(years_left(1.2), "~1 yr left");
+        assert_eq!(years_left(0.25), "<1 yr left");
+        assert_eq!(years_left(-2.0), "past typical lifespan");
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let thresholds: Thresholds = "50, 75%".parse().unwrap();
+        assert_eq!(thresholds.level(0.49), Level::Low);
+        assert_eq!(thresholds.level(0.6), Level::Mid);
+        assert_eq!(thresholds.level(0.75), Level::High);
+        assert!("80,60".parse::<Thresholds>().is_err());
+        assert!("50".parse::<Thresholds>().is_err());
+    }
+
+    #[test]
+    fn test_gradient_runs_cyan_to_red() {
+        let stops = Theme::default().gradient().unwrap();
+        assert_eq!(gradient_color(0.0, stops), [0, 190, 190]);
+        assert_eq!(gradient_color(0.5, stops), [230, 190, 0]);
+        assert_eq!(gradient_color(1.0, stops), [220, 40, 40]);
+        let bar = Bars::default().render_gradient(1.0, 3, stops);
+        assert!(bar.starts_with("\x1b[38;2;0;190;190m="));
+        assert!(bar.ends_with("\x1b[38;2;220;40;40m=\x1b[0m"));
+    }
+}
@@ -0,0 +1,847 @@
+//! Batch conversion from stdin lines or an intake file (CSV or JSON).
+
+use crate::bar::Bars;
+use crate::config::Config;
+use crate::ics;
+use crate::output::{self, Format, ReportTable, SharedAge};
+use crate::stage::Stages;
+use crate::standard::Standards;
+use crate::{
+    convert, print_note, print_warnings, validate_age, AppError, Conversion, WarningMode, HUMAN_MAX,
+};
+use animal_age::animal::Precision;
+use animal_age::date::Date;
+use animal_age::parse::{self, ParseError};
+use rayon::prelude::*;
+use serde::de::{Deserializer as _, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, SyncSender};
+use std::{iter, thread};
+
+/// Parses one batch line: either `animal,age` or a JSON object with the same fields
+/// as an intake file record.
+fn parse_batch_line(line: &str) -> Result<PetRecord, AppError> {
+    if line.starts_with('{') {
+        return serde_json::from_str(line).map_err(|e| AppError::InvalidInput(e.to_string()));
+    }
+
+    let (animal, age) = parse::parse_record(line).map_err(|err| match err {
+        ParseError::InvalidRecord => {
+            AppError::InvalidInput(format!("expected `animal,age`, got `{}`", line))
+        }
+        err => err.into(),
+    })?;
+
+    Ok(PetRecord {
+        name: None,
+        animal: animal.key().to_string(),
+        age: Some(age),
+        dob: None,
+    })
+}
+
+fn at(location: String) -> impl FnOnce(AppError) -> AppError {
+    move |source| AppError::Batch {
+        location,
+        source: Box::new(source),
+    }
+}
+
+/// Most pets converted in one parallel chunk, and the capacity of the queue between
+/// the reader thread and the workers; together they bound memory use however large
+/// the input is.
+const CHUNK_SIZE: usize = 4096;
+
+/// Converts `items` (each tagged with its location for error messages) on the rayon
+/// pool and passes each result and its rendering to `emit` in input order, printing
+/// each result's warnings to stderr first unless `warn` is off.
+///
+/// A reader thread feeds a bounded queue; each round converts whatever has arrived
+/// (up to [`CHUNK_SIZE`]) in parallel, emits it, and flushes `out`. Fast producers get
+/// large parallel chunks while slow ones still see every result as soon as it exists.
+fn stream<T, W, P, R, E>(
+    items: impl Iterator<Item = Result<(String, T), AppError>> + Send + 'static,
+    out: &mut W,
+    warn: bool,
+    process: P,
+    render: R,
+    mut emit: E,
+) -> Result<(), AppError>
+where
+    T: Send + 'static,
+    W: Write,
+    P: Fn(T) -> Result<Conversion, AppError> + Sync,
+    R: Fn(&Conversion) -> String + Sync,
+    E: FnMut(&mut W, Conversion, String) -> io::Result<()>,
+{
+    let (tx, rx) = mpsc::sync_channel(CHUNK_SIZE);
+    // Detached so an early error can return without waiting on a blocked reader.
+    thread::spawn(move || {
+        for item in items {
+            let failed = item.is_err();
+            if tx.send(item).is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    while let Ok(first) = rx.recv() {
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        let mut read_error = None;
+        for item in iter::once(first).chain(iter::from_fn(|| rx.try_recv().ok())) {
+            match item {
+                Ok(item) => chunk.push(item),
+                Err(err) => {
+                    read_error = Some(err);
+                    break;
+                }
+            }
+            if chunk.len() == CHUNK_SIZE {
+                break;
+            }
+        }
+
+        let results: Vec<_> = chunk
+            .into_par_iter()
+            .map(|(location, item)| {
+                process(item)
+                    .map(|conversion| {
+                        let rendered = render(&conversion);
+                        (conversion, rendered)
+                    })
+                    .map_err(at(location))
+            })
+            .collect();
+        for result in results {
+            let (conversion, rendered) = result?;
+            print_note(&conversion);
+            if warn {
+                print_warnings(&conversion);
+            }
+            emit(out, conversion, rendered)?;
+        }
+        out.flush()?;
+
+        if let Some(err) = read_error {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Streams a combined report in `format` to `out`. The text table has one header
+/// and columns sized from its first [`CHUNK_SIZE`] rows, and PDF and SVG, which
+/// lay out every pet at once, are refused rather than held in memory. A JSON
+/// report is indented only when `pretty` is set.
+fn stream_report<T: Send + 'static>(
+    items: impl Iterator<Item = Result<(String, T), AppError>> + Send + 'static,
+    process: impl Fn(T) -> Result<Conversion, AppError> + Sync,
+    format: Format,
+    pretty: bool,
+    bars: &Bars,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    let mut out = BufWriter::new(out);
+    let warn = !format.embeds_warnings();
+    match format {
+        Format::Pdf | Format::Svg => {
+            return Err(AppError::InvalidInput(
+                "pdf and svg output lay out every pet at once; write --stdin and --input \
+                 batches in another format"
+                    .to_string(),
+            ))
+        }
+        Format::Text => {
+            // The first block of rows sizes the columns; the rest stream through them.
+            let mut rows = Vec::with_capacity(CHUNK_SIZE);
+            let mut table: Option<ReportTable> = None;
+            stream(
+                items,
+                &mut out,
+                warn,
+                process,
+                |_| String::new(),
+                |out, conversion, _| {
+                    if let Some(table) = &table {
+                        table.write_row(&conversion, out)?;
+                        return Ok(());
+                    }
+                    rows.push(conversion);
+                    if rows.len() == CHUNK_SIZE {
+                        let sized = ReportTable::new(&rows, bars);
+                        sized.write_header(out)?;
+                        for row in rows.drain(..) {
+                            sized.write_row(&row, out)?;
+                        }
+                        table = Some(sized);
+                    }
+                    Ok(())
+                },
+            )?;
+            if table.is_none() {
+                output::print_table(&rows, bars, &mut out)?;
+            }
+            out.flush()?;
+        }
+        Format::Ics => {
+            let today = Date::today();
+            let process = |item| process(item).and_then(|c| ics::check_dob(&c).map(|()| c));
+            ics::write_start(&mut out)?;
+            stream(
+                items,
+                &mut out,
+                warn,
+                process,
+                |_| String::new(),
+                |out, conversion, _| ics::write_events(&conversion, today, out),
+            )?;
+            ics::write_end(&mut out)?;
+            out.flush()?;
+        }
+        Format::Ndjson
+        | Format::Csv
+        | Format::CsvLong
+        | Format::Tsv
+        | Format::Yaml
+        | Format::Toml
+        | Format::Xml
+        | Format::Html
+        | Format::Latex
+        | Format::Typst => {
+            if let Some(header) = output::header(format) {
+                writeln!(out, "{}", header)?;
+            }
+            let render = |c: &Conversion| output::render_line(c, format, bars);
+            stream(items, &mut out, warn, process, render, |out, _, line| {
+                writeln!(out, "{}", line)
+            })?;
+            if let Some(footer) = output::footer(format) {
+                writeln!(out, "{}", footer)?;
+            }
+            out.flush()?;
+        }
+        #[cfg(feature = "parquet")]
+        Format::Parquet => {
+            use crate::parquet_file::{ParquetWriter, ROW_GROUP_SIZE};
+            let mut writer = ParquetWriter::new()?;
+            let mut rows = Vec::with_capacity(ROW_GROUP_SIZE);
+            let mut failed = None;
+            stream(
+                items,
+                &mut out,
+                warn,
+                process,
+                |_| String::new(),
+                |out, conversion, _| {
+                    rows.push(conversion);
+                    if rows.len() == ROW_GROUP_SIZE {
+                        if let Err(err) = writer.write(&rows, out) {
+                            failed = Some(err);
+                            return Err(io::Error::other("Parquet write failed"));
+                        }
+                        rows.clear();
+                    }
+                    Ok(())
+                },
+            )
+            .map_err(|err| failed.take().unwrap_or(err))?;
+            writer.write(&rows, &mut out)?;
+            writer.finish(&mut out)?;
+            out.flush()?;
+        }
+        Format::Json => {
+            let newline = if pretty { "\n" } else { "" };
+            let render = |c: &Conversion| {
+                if pretty {
+                    output::render_array_item(c)
+                } else {
+                    output::render_line(c, Format::Json, bars)
+                }
+            };
+            let mut first = true;
+            let mut age = SharedAge::default();
+            write!(out, "{}", output::json_report_start(pretty))?;
+            stream(
+                items,
+                &mut out,
+                warn,
+                process,
+                render,
+                |out, conversion, item| {
+                    let separator = if first { "" } else { "," };
+                    first = false;
+                    age.add(conversion.age);
+                    write!(out, "{}{}{}", separator, newline, item)
+                },
+            )?;
+            writeln!(out, "{}", output::json_report_end(pretty, first, age.get()))?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Converts every non-blank, non-comment line of `input`, writing one result per line.
+/// Input that starts with `[` is read as a JSON array instead and reported as a whole.
+pub fn run_batch(
+    mut input: impl BufRead + Send + 'static,
+    format: Format,
+    pretty: bool,
+    settings: Settings,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    if starts_with_array(&mut input)? {
+        let pets = json_pets(input);
+        let process = |pet: PetRecord| pet.convert(Date::today(), &settings);
+        return stream_report(pets, process, format, pretty, &settings.bars, out);
+    }
+
+    let lines = input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| match line {
+            Ok(line) if line.trim().is_empty() || line.trim_start().starts_with('#') => None,
+            Ok(line) => Some(Ok((format!("Line {}", idx + 1), line))),
+            Err(err) => Some(Err(err.into())),
+        });
+    let today = Date::today();
+    let settings = &settings;
+    let process = move |line: String| parse_batch_line(line.trim())?.convert(today, settings);
+    // A JSON report is one document however it is fed; NDJSON is the format
+    // for a result per line.
+    if format.is_document() || format == Format::Json {
+        return stream_report(lines, process, format, pretty, &settings.bars, out);
+    }
+
+    let mut out = BufWriter::new(out);
+    if let Some(header) = output::header(format) {
+        writeln!(out, "{}", header)?;
+    }
+    stream(
+        lines,
+        &mut out,
+        !format.embeds_warnings(),
+        process,
+        |conversion| output::render_line(conversion, format, &settings.bars),
+        |out, _, line| writeln!(out, "{}", line),
+    )?;
+    if let Some(footer) = output::footer(format) {
+        writeln!(out, "{}", footer)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// One pet from an intake file. Either `age` or `dob` must be present; the field
+/// names mirror the JSON output so results can be fed back in.
+#[derive(Deserialize)]
+struct PetRecord {
+    name: Option<String>,
+    #[serde(alias = "species", alias = "type")]
+    animal: String,
+    age: Option<f32>,
+    dob: Option<Date>,
+}
+
+impl PetRecord {
+    fn age(&self, today: Date) -> Result<f32, AppError> {
+        match (self.age, self.dob) {
+            (Some(age), _) => Ok(age),
+            (None, Some(dob)) if dob > today => Err(AppError::InvalidAge(format!(
+                "date of birth {} is in the future",
+                dob
+            ))),
+            (None, Some(dob)) => Ok(dob.years_until(today)),
+            (None, None) => Err(AppError::InvalidInput(
+                "pet needs either an age or a dob".to_string(),
+            )),
+        }
+    }
+
+    fn convert(self, today: Date, settings: &Settings) -> Result<Conversion, AppError> {
+        let age = self.age(today)?;
+        validate_age(age)?;
+        let mut conversion = Conversion {
+            name: self.name,
+            dob: self.dob,
+            human_max: settings.human_max,
+            survive_to: settings.survive_to,
+            ..convert(&self.animal, age)?
+        };
+        settings
+            .standards
+            .apply(settings.standard.as_deref(), &mut conversion)?;
+        settings.stages.apply(&mut conversion)?;
+        conversion.set_precision(settings.precision);
+        conversion.check_warnings(settings.warnings)?;
+        Ok(conversion)
+    }
+}
+
+/// How every pet in a batch is converted: the standards and life stages from
+/// the config file, and the flags that apply to each record.
+pub struct Settings {
+    standards: Standards,
+    stages: Stages,
+    /// `--standard`, else each species' default.
+    pub standard: Option<String>,
+    /// The human lifespan from `--human-max` or `--country`.
+    pub human_max: f32,
+    /// `--survive-to`.
+    pub survive_to: Option<f32>,
+    pub warnings: WarningMode,
+    pub precision: Precision,
+    /// Where the bars of an HTML report change color.
+    pub bars: Bars,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            standards: Standards::default(),
+            stages: Stages::default(),
+            standard: None,
+            human_max: HUMAN_MAX,
+            survive_to: None,
+            warnings: WarningMode::default(),
+            precision: Precision::default(),
+            bars: Bars::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Settings with the config file's standards, life stages, and bar thresholds
+    /// and every flag left at its default.
+    pub fn load() -> Result<Settings, AppError> {
+        let config = Config::load()?;
+        Ok(Settings {
+            standards: Standards::from_config(&config),
+            stages: Stages::from_config(&config),
+            bars: Bars {
+                thresholds: config.thresholds.unwrap_or_default(),
+                ..Bars::default()
+            },
+            ..Settings::default()
+        })
+    }
+}
+
+/// Streams the rows of a CSV file with `name`, `species`, and `age` or `dob` columns.
+fn run_csv(
+    path: &Path,
+    settings: &Settings,
+    format: Format,
+    pretty: bool,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(csv_error)?;
+    let headers: csv::StringRecord = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(str::to_lowercase)
+        .collect();
+
+    let records = reader.into_records().map(|record| {
+        let record = record.map_err(csv_error)?;
+        let line = record.position().map_or(0, |p| p.line());
+        Ok((format!("Line {}", line), record))
+    });
+    let today = Date::today();
+    stream_report(
+        records,
+        |record| {
+            let pet: PetRecord = record.deserialize(Some(&headers)).map_err(csv_error)?;
+            pet.convert(today, settings)
+        },
+        format,
+        pretty,
+        &settings.bars,
+        out,
+    )
+}
+
+type PetItem = Result<(String, PetRecord), AppError>;
+
+/// Reads a JSON array of `{name, animal, age}` (or `dob`) objects. A thread parses
+/// one element at a time into a bounded queue, so like CSV and line input the
+/// array is never held in memory whole; a syntax error ends the stream.
+fn json_pets(input: impl Read + Send + 'static) -> impl Iterator<Item = PetItem> {
+    let (tx, rx) = mpsc::sync_channel(CHUNK_SIZE);
+    thread::spawn(move || {
+        let mut deserializer = serde_json::Deserializer::from_reader(input);
+        let parsed = deserializer
+            .deserialize_seq(PetSeq(&tx))
+            .and_then(|()| deserializer.end());
+        if let Err(err) = parsed {
+            // The reader may have stopped at an earlier error; nobody is listening then.
+            let _ = tx.send(Err(AppError::InvalidInput(err.to_string())));
+        }
+    });
+    rx.into_iter()
+}
+
+/// Sends each element of a JSON array down the queue as soon as it is parsed.
+struct PetSeq<'a>(&'a SyncSender<PetItem>);
+
+impl<'de> Visitor<'de> for PetSeq<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of pets")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut count = 0;
+        while let Some(pet) = seq.next_element()? {
+            count += 1;
+            if self.0.send(Ok((format!("Record {}", count), pet))).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn csv_error(err: csv::Error) -> AppError {
+    let message = err.to_string();
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => AppError::Io(err),
+        _ => AppError::InvalidInput(message),
+    }
+}
+
+/// Skips leading whitespace and reports whether the next byte opens a JSON array.
+fn starts_with_array(input: &mut impl BufRead) -> io::Result<bool> {
+    loop {
+        let buf = input.fill_buf()?;
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(idx) => {
+                let is_array = buf[idx] == b'[';
+                input.consume(idx);
+                return Ok(is_array);
+            }
+            None if buf.is_empty() => return Ok(false),
+            None => {
+                let len = buf.len();
+                input.consume(len);
+            }
+        }
+    }
+}
+
+/// Converts an intake file (CSV, or JSON when the extension is `.json`) and writes
+/// the combined report to `out`.
+pub fn run_input(
+    path: &Path,
+    format: Format,
+    pretty: bool,
+    settings: Settings,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        let pets = json_pets(BufReader::new(File::open(path)?));
+        let process = |pet: PetRecord| pet.convert(Date::today(), &settings);
+        stream_report(pets, process, format, pretty, &settings.bars, out)
+    } else {
+        run_csv(path, &settings, format, pretty, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch_line() {
+        let record = parse_batch_line("cat, 3").unwrap();
+        assert_eq!(record.animal, "cat");
+        assert_eq!(record.age, Some(3.0));
+
+        let record = parse_batch_line(r#"{"animal": "horse", "age": 10}"#).unwrap();
+        assert_eq!(record.animal, "horse");
+        assert_eq!(record.age, Some(10.0));
+
+        assert!(parse_batch_line("cat").is_err());
+        assert!(parse_batch_line("cat,old").is_err());
+        assert_eq!(parse_batch_line("cat, 18mo").unwrap().age, Some(1.5));
+        // Animals are read as -t reads them, prefixes included.
+        assert_eq!(parse_batch_line("Ham, 1").unwrap().animal, "hamster");
+        assert!(matches!(
+            parse_batch_line("go, 1"),
+            Err(AppError::AmbiguousAnimal(..))
+        ));
+        assert!(parse_batch_line(&format!("cat, {}", "1".repeat(300))).is_err());
+    }
+
+    #[test]
+    fn test_pet_age_from_dob() {
+        let today: Date = "2024-06-01".parse().unwrap();
+        let pet = PetRecord {
+            name: Some("Whiskers".to_string()),
+            animal: "cat".to_string(),
+            age: None,
+            dob: Some("2021-06-01".parse().unwrap()),
+        };
+        assert!((pet.age(today).unwrap() - 3.0).abs() < 0.01);
+
+        let pet = PetRecord {
+            dob: Some("2025-01-01".parse().unwrap()),
+            ..pet
+        };
+        assert!(pet.age(today).is_err());
+    }
+
+    #[test]
+    fn test_json_pets() {
+        let input = r#"[
+            {"name": "Whiskers", "animal": "cat", "age": 3},
+            {"animal": "horse", "age": 10.0, "human_age": 46.5}
+        ]"#;
+        let today = Date::today();
+        let conversions: Vec<Conversion> = json_pets(input.as_bytes())
+            .map(|item| item.and_then(|(_, pet)| pet.convert(today, &Settings::default())))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(conversions.len(), 2);
+        assert_eq!(conversions[0].name.as_deref(), Some("Whiskers"));
+        assert_eq!(conversions[0].human_age, 29.0);
+        assert_eq!(conversions[1].name, None);
+
+        let mut items = json_pets(r#"{"animal": "cat"}"#.as_bytes());
+        assert!(items.next().unwrap().is_err());
+        assert!(items.next().is_none());
+
+        // Records before a syntax error still come through, in order.
+        let items: Vec<_> = json_pets(r#"[{"animal": "cat", "age": 3}, {"#.as_bytes()).collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap().0, "Record 1");
+        assert!(items[1].is_err());
+    }
+
+    #[test]
+    fn test_rejects_ages_out_of_range() {
+        for line in ["cat, inf", "cat, NaN", "cat, 201", "cat, -1"] {
+            let converted = parse_batch_line(line)
+                .and_then(|pet| pet.convert(Date::today(), &Settings::default()));
+            assert!(
+                matches!(converted, Err(AppError::InvalidAge(_))),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_unrounded_ages() {
+        let convert = |precision| {
+            parse_batch_line("cat, 0.3")
+                .unwrap()
+                .convert(
+                    Date::today(),
+                    &Settings {
+                        precision,
+                        ..Settings::default()
+                    },
+                )
+                .unwrap()
+                .human_age
+        };
+        assert_eq!(convert(Precision::default()), 3.8);
+        assert!((convert(Precision::Exact) - 3.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_document_sinks_stream_or_refuse() {
+        let run = |input: &'static str, format| {
+            let mut out = Vec::new();
+            run_batch(
+                input.as_bytes(),
+                format,
+                false,
+                Settings::default(),
+                &mut out,
+            )
+            .map(|()| String::from_utf8(out).unwrap())
+        };
+        assert!(run("cat, 3\n", Format::Pdf).is_err());
+        assert!(run("cat, 3\n", Format::Svg).is_err());
+
+        let calendar = run(r#"{"animal": "cat", "dob": "2020-01-01"}"#, Format::Ics).unwrap();
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("BEGIN:VEVENT"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        let err = run("cat, 3\n", Format::Ics).unwrap_err();
+        assert!(err.to_string().starts_with("Line 1: "), "{}", err);
+
+        let pets = r#"[{"animal": "cat", "age": 3}, {"animal": "horse", "age": 10}]"#;
+        let table = run(pets, Format::Text).unwrap();
+        assert_eq!(table.matches("Species").count(), 1);
+        assert!(table.contains("horse"));
+
+        // Past the first block, rows keep its column widths under one header.
+        let mut pets = vec![r#"{"animal": "cat", "age": 3}"#; CHUNK_SIZE];
+        pets.push(r#"{"animal": "horse", "age": 9}"#);
+        let pets = format!("[{}]", pets.join(","));
+        let mut out = Vec::new();
+        run_batch(
+            io::Cursor::new(pets),
+            Format::Text,
+            false,
+            Settings::default(),
+            &mut out,
+        )
+        .unwrap();
+        let table = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), CHUNK_SIZE + 2);
+        assert_eq!(table.matches("Species").count(), 1);
+        assert_eq!(lines[1].len(), lines[CHUNK_SIZE + 1].len());
+        assert!(lines[CHUNK_SIZE + 1].contains(" horse "));
+    }
+
+    #[test]
+    fn test_standard() {
+        let settings = Settings {
+            standard: Some("aaha".to_string()),
+            ..Settings::default()
+        };
+        let convert = |line| {
+            parse_batch_line(line)
+                .unwrap()
+                .convert(Date::today(), &settings)
+        };
+        let cat = convert("cat, 3").unwrap();
+        assert_eq!(cat.formula.name(), "three_stage");
+        assert_eq!(cat.human_age, 28.0);
+        assert!(convert("horse, 10").is_err());
+    }
+
+    #[test]
+    fn test_human_max_and_survive_to() {
+        let settings = Settings {
+            human_max: 100.0,
+            survive_to: Some(15.0),
+            ..Settings::default()
+        };
+        let cat = parse_batch_line("cat, 3")
+            .unwrap()
+            .convert(Date::today(), &settings)
+            .unwrap();
+        assert_eq!(cat.human_max, 100.0);
+        assert_eq!(cat.survive_to, Some(15.0));
+    }
+
+    #[test]
+    fn test_warning_modes() {
+        // Five years is well past a hamster's lifespan.
+        let convert = |mode| {
+            parse_batch_line("hamster, 5").unwrap().convert(
+                Date::today(),
+                &Settings {
+                    warnings: mode,
+                    ..Settings::default()
+                },
+            )
+        };
+        assert_eq!(convert(WarningMode::Print).unwrap().warnings.len(), 1);
+        assert!(convert(WarningMode::Ignore).unwrap().warnings.is_empty());
+        assert!(matches!(
+            convert(WarningMode::Fail),
+            Err(AppError::Strict(_))
+        ));
+    }
+
+    #[test]
+    fn test_stream_preserves_order_across_chunks() {
+        let count = CHUNK_SIZE * 2 + 7;
+        let items = (0..count).map(|i| Ok((format!("Line {}", i + 1), i as f32 / 1000.0)));
+        let mut ages = Vec::new();
+        stream(
+            items,
+            &mut io::sink(),
+            true,
+            |age| convert("cat", age),
+            |conversion| conversion.age.to_string(),
+            |_, conversion, rendered| {
+                assert_eq!(rendered, conversion.age.to_string());
+                ages.push(conversion.age);
+                Ok(())
+            },
+        )
+        .unwrap();
+        let expected: Vec<f32> = (0..count).map(|i| i as f32 / 1000.0).collect();
+        assert_eq!(ages, expected);
+    }
+
+    #[test]
+    fn test_stream_reports_first_error_location() {
+        let items = ["cat", "kat", "dgo"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, animal)| Ok((format!("Line {}", i + 1), animal)));
+        let err = stream(
+            items,
+            &mut io::sink(),
+            true,
+            |animal| convert(animal, 1.0),
+            |_| String::new(),
+            |_, _, _| Ok(()),
+        )
+        .unwrap_err();
+        assert!(matches!(err, AppError::Batch { ref location, .. } if location == "Line 2"));
+    }
+
+    #[test]
+    fn test_streamed_json_is_the_report() {
+        for pretty in [false, true] {
+            for ages in [vec![], vec![3.0, 10.0], vec![3.0, 3.0]] {
+                let conversions: Vec<Conversion> = ages
+                    .iter()
+                    .map(|&age| convert("cat", age).unwrap())
+                    .collect();
+                let mut whole = Vec::new();
+                output::print_json_report(&conversions, pretty, &mut whole).unwrap();
+
+                let items = ages
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, age)| Ok((format!("Line {}", i + 1), age)));
+                let mut streamed = Vec::new();
+                let process = |age| convert("cat", age);
+                stream_report(
+                    items,
+                    process,
+                    Format::Json,
+                    pretty,
+                    &Bars::default(),
+                    &mut streamed,
+                )
+                .unwrap();
+                assert_eq!(
+                    String::from_utf8(streamed).unwrap(),
+                    String::from_utf8(whole).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_starts_with_array() {
+        let mut input = "  \n [1]".as_bytes();
+        assert!(starts_with_array(&mut input).unwrap());
+        let mut input = "cat,3".as_bytes();
+        assert!(!starts_with_array(&mut input).unwrap());
+    }
+}
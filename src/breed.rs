@@ -0,0 +1,119 @@
+//! Breed reference data: which conversion formula a breed uses and how long it
+//! typically lives. The tables live in `data/` and are embedded at compile time.
+
+use crate::animal::Animal;
+use std::sync::OnceLock;
+
+const DOG_BREEDS: &str = include_str!("../data/dog_breeds.csv");
+const CAT_BREEDS: &str = include_str!("../data/cat_breeds.csv");
+
+/// A known breed and the facts used to convert its age.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breed {
+    pub key: &'static str,
+    pub name: &'static str,
+    /// The animal type whose formula applies, e.g. `medium_dog` for a beagle or
+    /// `cat` for a Maine Coon.
+    pub animal: Animal,
+    /// Typical lifespan range in years, `(min, max)`.
+    pub lifespan: (f32, f32),
+}
+
+impl Breed {
+    /// Every known breed, sorted by key.
+    pub fn all() -> &'static [Breed] {
+        static BREEDS: OnceLock<Vec<Breed>> = OnceLock::new();
+        BREEDS.get_or_init(|| {
+            let mut breeds: Vec<Breed> = [DOG_BREEDS, CAT_BREEDS]
+                .into_iter()
+                .flat_map(parse_table)
+                .collect();
+            breeds.sort_by_key(|breed| breed.key);
+            breeds
+        })
+    }
+
+    /// Looks up a breed by key or display name, ignoring case, spaces, hyphens,
+    /// and apostrophes (`"German Shepherd Dog"`, `"german-shepherd"`).
+    pub fn find(name: &str) -> Option<&'static Breed> {
+        let wanted = normalize(name);
+        Breed::all()
+            .iter()
+            .find(|breed| breed.key == wanted || normalize(breed.name) == wanted)
+    }
+
+    /// The upper end of the typical lifespan, used as the lifespan bar's maximum.
+    pub fn max_lifespan(&self) -> f32 {
+        self.lifespan.1
+    }
+
+    /// The middle of the typical lifespan range.
+    pub fn typical_lifespan(&self) -> f32 {
+        (self.lifespan.0 + self.lifespan.1) / 2.0
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-', '\''], "_")
+}
+
+/// Parses an embedded `key,name,animal,lifespan_min,lifespan_max` table. The data
+/// ships with the binary, so malformed rows are a bug rather than user error.
+fn parse_table(data: &'static str) -> impl Iterator<Item = Breed> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [key, name, animal, min, max] = fields[..] else {
+                panic!("malformed breed row: {}", line);
+            };
+            let years = |s: &str| -> f32 {
+                s.parse()
+                    .unwrap_or_else(|_| panic!("bad lifespan in breed row: {}", line))
+            };
+            Breed {
+                key,
+                name,
+                animal: Animal::from_key(animal)
+                    .unwrap_or_else(|| panic!("unknown animal in breed row: {}", line)),
+                lifespan: (years(min), years(max)),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breed_table_is_well_formed() {
+        let breeds = Breed::all();
+        assert!(breeds.len() > 200);
+        for pair in breeds.windows(2) {
+            assert!(pair[0].key < pair[1].key, "duplicate {}", pair[1].key);
+        }
+        for breed in breeds {
+            assert!(breed.animal.is_dog() || breed.animal == Animal::Cat);
+            assert!(breed.lifespan.0 <= breed.lifespan.1, "{}", breed.key);
+        }
+    }
+
+    #[test]
+    fn test_find_breed() {
+        let beagle = Breed::find("beagle").unwrap();
+        assert_eq!(beagle.animal, Animal::MediumDog);
+        assert_eq!(beagle.max_lifespan(), 15.0);
+        assert_eq!(beagle.typical_lifespan(), 13.5);
+        assert_eq!(
+            Breed::find("German Shepherd Dog").unwrap().key,
+            "german_shepherd"
+        );
+        assert_eq!(
+            Breed::find("Yorkshire-Terrier").unwrap().key,
+            "yorkshire_terrier"
+        );
+        assert_eq!(Breed::find("Maine Coon").unwrap().animal, Animal::Cat);
+        assert!(Breed::find("dragon").is_none());
+    }
+}
@@ -0,0 +1,309 @@
+//! The `animal-age calibrate` subcommand: fits a formula to known pairs of
+//! animal and human ages and saves it as a custom standard in the config file.
+
+use crate::config::Config;
+use crate::AppError;
+use animal_age::animal::{Animal, Formula, Standard};
+use clap::ValueEnum;
+
+#[derive(clap::Args)]
+pub struct CalibrateArgs {
+    /// Animal type the formula is for
+    #[arg(short = 't', long = "type", value_name = "ANIMAL")]
+    animal: String,
+
+    /// Known ages as ANIMAL_AGE=HUMAN_AGE pairs, e.g. 1=15,2=24,5=36
+    #[arg(
+        long = "points",
+        value_name = "POINTS",
+        value_delimiter = ',',
+        value_parser = parse_point,
+        required = true
+    )]
+    points: Vec<(f32, f32)>,
+
+    /// Model to fit
+    #[arg(long = "model", value_enum, default_value_t = Model::TwoStage)]
+    model: Model,
+
+    /// Name of the custom standard to save the formula under
+    #[arg(long = "standard", value_name = "NAME", default_value = "calibrated")]
+    standard: String,
+
+    /// Print the fitted formula without saving it
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+}
+
+/// The formula models `calibrate` can fit.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Model {
+    /// Two straight lines from birth, meeting at a fitted age
+    #[value(name = "two_stage")]
+    TwoStage,
+    #[value(name = "linear")]
+    Linear,
+    #[value(name = "logarithmic")]
+    Logarithmic,
+}
+
+/// Parses one `ANIMAL_AGE=HUMAN_AGE` pair.
+fn parse_point(input: &str) -> Result<(f32, f32), String> {
+    let (age, human) = input
+        .split_once('=')
+        .ok_or_else(|| format!("expected ANIMAL_AGE=HUMAN_AGE, got '{}'", input))?;
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite() && *value >= 0.0)
+            .ok_or_else(|| format!("'{}' is not an age in years", value.trim()))
+    };
+    let age = parse(age)?;
+    if age == 0.0 {
+        return Err("animal ages must be after birth".to_string());
+    }
+    Ok((age, parse(human)?))
+}
+
+/// The key a formula is saved under: a built-in animal's own key (so aliases
+/// work), or else any species name made of letters, digits, and underscores,
+/// for exotic pets without a built-in formula.
+fn species_key(input: &str) -> Result<String, AppError> {
+    if let Some(animal) = Animal::from_key(input) {
+        return Ok(animal.key().to_string());
+    }
+    let key = input.trim().to_lowercase();
+    if key.is_empty() || !key.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(AppError::InvalidInput(format!(
+            "'{}' is not a species name; use letters, digits, and underscores, like bearded_dragon",
+            input.trim()
+        )));
+    }
+    Ok(key)
+}
+
+pub fn run(args: &CalibrateArgs) -> Result<(), AppError> {
+    let animal = species_key(&args.animal)?;
+    if args.standard.parse::<Standard>().is_ok() {
+        return Err(AppError::InvalidInput(format!(
+            "'{}' is a built-in standard; pick another name with --standard",
+            args.standard
+        )));
+    }
+    let formula = fit(args.model, &args.points)?;
+    let parameters: Vec<String> = formula
+        .parameters()
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    println!(
+        "Fitted {} formula for {}: {} (RMS error {:.1} human years)",
+        formula.name(),
+        animal,
+        parameters.join(", "),
+        rms_error(formula, &args.points)
+    );
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let path = Config::save_formula(&args.standard, &animal, formula)?;
+    println!(
+        "Saved to {} as the '{}' standard for {}.",
+        path.display(),
+        args.standard,
+        animal
+    );
+    if Animal::from_key(&animal).is_some() {
+        println!("Use it with -t {} --standard {}.", animal, args.standard);
+    }
+    Ok(())
+}
+
+/// Least-squares fit of `model` to `(animal_age, human_age)` points, with the
+/// parameters rounded to two decimals.
+fn fit(model: Model, points: &[(f32, f32)]) -> Result<Formula, AppError> {
+    let ages = || points.iter().map(|&(age, _)| age);
+    let distinct = ages().filter(|&age| age != points[0].0).count();
+    if points.len() < 2 || distinct == 0 {
+        return Err(AppError::InvalidInput(
+            "calibration needs points at two or more different ages".to_string(),
+        ));
+    }
+    let round = |value: f64| ((value * 100.0).round() / 100.0) as f32;
+    let formula = match model {
+        Model::Linear => {
+            let (offset, rate) = line_fit(points.iter().copied());
+            Formula::Linear {
+                offset: round(offset),
+                rate: round(rate),
+            }
+        }
+        Model::Logarithmic => {
+            let (offset, scale) = line_fit(points.iter().map(|&(age, human)| (age.ln(), human)));
+            if scale <= 0.0 {
+                return Err(AppError::InvalidInput(
+                    "human ages must rise with age to fit a logarithmic model".to_string(),
+                ));
+            }
+            Formula::Logarithmic {
+                scale: round(scale),
+                offset: round(offset),
+            }
+        }
+        Model::TwoStage => {
+            let oldest = ages().fold(0.0, f32::max);
+            // Try the stage boundary at each point and on a fine grid between.
+            let candidates = ages()
+                .filter(|&age| age < oldest)
+                .chain((1..100).map(|step| oldest * step as f32 / 100.0));
+            let (early_years, early_rate, later_rate) = candidates
+                .filter_map(|early_years| two_stage_fit(points, early_years))
+                .min_by(|a, b| a.3.total_cmp(&b.3))
+                .map(|(years, early, later, _)| (years, early, later))
+                .ok_or_else(|| {
+                    AppError::InvalidInput(
+                        "human ages must rise with age to fit a two-stage model".to_string(),
+                    )
+                })?;
+            Formula::TwoStage {
+                early_years: round(early_years as f64),
+                early_rate: round(early_rate),
+                later_rate: round(later_rate),
+            }
+        }
+    };
+    formula.validate().map_err(|err| {
+        AppError::InvalidInput(format!(
+            "human ages must rise with age to fit a {} model ({})",
+            formula.name(),
+            err
+        ))
+    })?;
+    Ok(formula)
+}
+
+/// Ordinary least squares for `y = intercept + slope * x`.
+fn line_fit(points: impl Iterator<Item = (f32, f32)> + Clone) -> (f64, f64) {
+    let n = points.clone().count() as f64;
+    let (sx, sy, sxx, sxy) = points.fold((0.0, 0.0, 0.0, 0.0), |(sx, sy, sxx, sxy), (x, y)| {
+        let (x, y) = (x as f64, y as f64);
+        (sx + x, sy + y, sxx + x * x, sxy + x * y)
+    });
+    let slope = (n * sxy - sx * sy) / (n * sxx - sx * sx);
+    ((sy - slope * sx) / n, slope)
+}
+
+/// Least-squares rates of a two-stage formula with its boundary at
+/// `early_years`, and the sum of squared errors. `None` when the rates are
+/// undetermined or would run backwards.
+fn two_stage_fit(points: &[(f32, f32)], early_years: f32) -> Option<(f32, f64, f64, f64)> {
+    let features = |age: f32| {
+        let (age, early_years) = (age as f64, early_years as f64);
+        (age.min(early_years), (age - early_years).max(0.0))
+    };
+    let (mut s11, mut s12, mut s22, mut s1y, mut s2y) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for &(age, human) in points {
+        let ((x1, x2), y) = (features(age), human as f64);
+        s11 += x1 * x1;
+        s12 += x1 * x2;
+        s22 += x2 * x2;
+        s1y += x1 * y;
+        s2y += x2 * y;
+    }
+    let det = s11 * s22 - s12 * s12;
+    if det.abs() < 1e-9 {
+        return None;
+    }
+    let early_rate = (s1y * s22 - s2y * s12) / det;
+    let later_rate = (s2y * s11 - s1y * s12) / det;
+    if early_rate < 0.0 || later_rate < 0.0 {
+        return None;
+    }
+    let error = points
+        .iter()
+        .map(|&(age, human)| {
+            let (x1, x2) = features(age);
+            (early_rate * x1 + later_rate * x2 - human as f64).powi(2)
+        })
+        .sum();
+    Some((early_years, early_rate, later_rate, error))
+}
+
+/// Root-mean-square distance, in human years, between `formula` and the points.
+fn rms_error(formula: Formula, points: &[(f32, f32)]) -> f32 {
+    let sum: f32 = points
+        .iter()
+        .map(|&(age, human)| (formula.human_years(age) - human).powi(2))
+        .sum();
+    (sum / points.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_point() {
+        assert_eq!(parse_point("1.5=20"), Ok((1.5, 20.0)));
+        assert!(parse_point("1:20").is_err());
+        assert!(parse_point("0=0").is_err());
+        assert!(parse_point("2=-3").is_err());
+    }
+
+    #[test]
+    fn test_fit_recovers_formulas() {
+        let cat = Animal::Cat.formula();
+        let points: Vec<(f32, f32)> = [0.5, 1.0, 2.0, 4.0, 10.0]
+            .map(|age| (age, cat.human_years(age)))
+            .to_vec();
+        assert_eq!(fit(Model::TwoStage, &points).unwrap(), cat);
+
+        let horse = Animal::Horse.formula();
+        let points = [
+            (5.0, horse.human_years(5.0)),
+            (20.0, horse.human_years(20.0)),
+        ];
+        assert_eq!(fit(Model::Linear, &points).unwrap(), horse);
+
+        let log = Formula::Logarithmic {
+            scale: 16.0,
+            offset: 31.0,
+        };
+        let points = [2.0, 5.0, 9.0].map(|age| (age, log.human_years(age)));
+        assert_eq!(fit(Model::Logarithmic, &points).unwrap(), log);
+    }
+
+    #[test]
+    fn test_fit_needs_rising_points() {
+        assert!(fit(Model::TwoStage, &[(2.0, 20.0)]).is_err());
+        assert!(fit(Model::Linear, &[(2.0, 20.0), (2.0, 24.0)]).is_err());
+        assert!(fit(Model::Logarithmic, &[(1.0, 30.0), (4.0, 10.0)]).is_err());
+        let err = fit(Model::Linear, &[(1.0, 30.0), (5.0, 10.0)]).unwrap_err();
+        assert!(
+            err.to_string().contains("rate must be above zero"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_species_key() {
+        assert_eq!(species_key("Kitten").unwrap(), "cat");
+        assert_eq!(species_key(" Axolotl ").unwrap(), "axolotl");
+        assert!(species_key("sugar glider").is_err());
+        assert!(species_key("").is_err());
+    }
+
+    #[test]
+    fn test_rms_error() {
+        let formula = Formula::Linear {
+            offset: 0.0,
+            rate: 10.0,
+        };
+        assert_eq!(rms_error(formula, &[(1.0, 10.0), (2.0, 20.0)]), 0.0);
+        assert_eq!(rms_error(formula, &[(1.0, 13.0), (2.0, 17.0)]), 3.0);
+    }
+}
@@ -0,0 +1,256 @@
+//! The species catalog printed by `--list`, as text or in a machine-readable
+//! format for scripts and GUIs that offer the supported animals.
+
+use crate::output::{tsv_line, Format, SCHEMA_VERSION};
+use crate::AppError;
+use animal_age::animal::{Animal, Category};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// One supported animal, using the field names of the JSON output.
+#[derive(Serialize)]
+struct Entry {
+    key: &'static str,
+    description: &'static str,
+    typical_lifespan: f32,
+    max_lifespan: f32,
+    aliases: &'static [&'static str],
+    categories: Vec<&'static str>,
+    formula: &'static str,
+}
+
+impl From<Animal> for Entry {
+    fn from(animal: Animal) -> Entry {
+        Entry {
+            key: animal.key(),
+            description: animal.description(),
+            typical_lifespan: animal.typical_lifespan(),
+            max_lifespan: animal.max_lifespan(),
+            aliases: animal.aliases(),
+            categories: animal.categories().iter().map(|c| c.key()).collect(),
+            formula: animal.formula().name(),
+        }
+    }
+}
+
+impl Entry {
+    /// The CSV and TSV fields; aliases and categories are separated by `;`.
+    fn record(&self) -> [String; 7] {
+        [
+            self.key.to_string(),
+            self.description.to_string(),
+            self.typical_lifespan.to_string(),
+            self.max_lifespan.to_string(),
+            self.aliases.join(";"),
+            self.categories.join(";"),
+            self.formula.to_string(),
+        ]
+    }
+}
+
+const HEADER: [&str; 7] = [
+    "key",
+    "description",
+    "typical_lifespan",
+    "max_lifespan",
+    "aliases",
+    "categories",
+    "formula",
+];
+
+/// The JSON document: every animal, tagged like the conversion report.
+#[derive(Serialize)]
+struct Catalog {
+    schema_version: u32,
+    version: &'static str,
+    animals: Vec<Entry>,
+}
+
+/// Whether `animal` is listed by `--filter TEXT`: TEXT appears, ignoring case,
+/// in its key, description, an alias, or the key or title of one of its groups.
+fn matches(animal: Animal, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    let groups = animal
+        .categories()
+        .iter()
+        .flat_map(|category| [category.key(), category.title()]);
+    [animal.key(), animal.description()]
+        .into_iter()
+        .chain(animal.aliases().iter().copied())
+        .chain(groups)
+        .any(|text| text.to_lowercase().contains(&filter))
+}
+
+/// Writes the supported animals that match `filter`, or all of them, in
+/// `format`: text, JSON, NDJSON, CSV, or TSV.
+pub fn print(
+    format: Format,
+    filter: Option<&str>,
+    pretty: bool,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    let animals: Vec<Animal> = Animal::ALL
+        .into_iter()
+        .filter(|&animal| filter.is_none_or(|filter| matches(animal, filter)))
+        .collect();
+    let entries = || animals.iter().copied().map(Entry::from);
+    match format {
+        Format::Text => print_text(&animals, filter, out)?,
+        Format::Json => {
+            let catalog = Catalog {
+                schema_version: SCHEMA_VERSION,
+                version: env!("CARGO_PKG_VERSION"),
+                animals: entries().collect(),
+            };
+            let json = if pretty {
+                serde_json::to_string_pretty(&catalog)
+            } else {
+                serde_json::to_string(&catalog)
+            };
+            writeln!(out, "{}", json.unwrap())?;
+        }
+        Format::Ndjson => {
+            for entry in entries() {
+                writeln!(out, "{}", serde_json::to_string(&entry).unwrap())?;
+            }
+        }
+        Format::Csv => print_csv(entries(), out)?,
+        Format::Tsv => {
+            writeln!(out, "{}", tsv_line(HEADER))?;
+            for entry in entries() {
+                writeln!(out, "{}", tsv_line(entry.record()))?;
+            }
+        }
+        _ => {
+            return Err(AppError::InvalidInput(
+                "--list is written as text, json, ndjson, csv, or tsv".to_string(),
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn print_csv(entries: impl Iterator<Item = Entry>, out: &mut dyn Write) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(HEADER)?;
+    for entry in entries {
+        writer.write_record(entry.record())?;
+    }
+    writer.flush()
+}
+
+/// Lists `animals`, those without a [`Category`] first and then each category
+/// under its own heading, naming the group `-t` accepts for it.
+fn print_text(animals: &[Animal], filter: Option<&str>, out: &mut dyn Write) -> io::Result<()> {
+    if animals.is_empty() {
+        return writeln!(
+            out,
+            "No animals match '{}'.",
+            filter.unwrap_or_default().trim()
+        );
+    }
+    let width = animals.iter().map(|a| a.key().len()).max().unwrap_or(0);
+    let in_group = |category: Option<Category>| -> Vec<Animal> {
+        animals
+            .iter()
+            .copied()
+            .filter(|a| a.categories().first().copied() == category)
+            .collect()
+    };
+    let print = |members: &[Animal], out: &mut dyn Write| {
+        for animal in members {
+            writeln!(out, "  {:width$} - {}", animal.key(), animal.description())?;
+        }
+        Ok::<(), io::Error>(())
+    };
+    writeln!(out, "Available animals:")?;
+    let ungrouped = in_group(None);
+    if !ungrouped.is_empty() {
+        writeln!(out)?;
+        print(&ungrouped, out)?;
+    }
+    for category in Category::ALL {
+        let members = in_group(Some(category));
+        if members.is_empty() {
+            continue;
+        }
+        writeln!(out, "\n{} (-t {}):\n", category.title(), category.key())?;
+        print(&members, out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: Format) -> String {
+        render_filtered(format, None)
+    }
+
+    fn render_filtered(format: Format, filter: Option<&str>) -> String {
+        let mut out = Vec::new();
+        print(format, filter, false, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_json_catalog() {
+        let catalog: serde_json::Value = serde_json::from_str(&render(Format::Json)).unwrap();
+        let animals = catalog["animals"].as_array().unwrap();
+        assert_eq!(animals.len(), Animal::ALL.len());
+        let chicken = animals.iter().find(|a| a["key"] == "chicken").unwrap();
+        assert_eq!(chicken["aliases"], serde_json::json!(["hen"]));
+        assert_eq!(chicken["categories"], serde_json::json!(["farm", "birds"]));
+        assert_eq!(animals[0]["formula"], "two_stage");
+    }
+
+    #[test]
+    fn test_csv_catalog() {
+        let csv = render(Format::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("key,description,typical_lifespan,max_lifespan,aliases,categories,formula")
+        );
+        assert_eq!(lines.count(), Animal::ALL.len());
+        assert!(csv.contains(",bunny,,"));
+        assert_eq!(render(Format::Tsv).lines().count(), Animal::ALL.len() + 1);
+    }
+
+    #[test]
+    fn test_filter() {
+        let keys = |filter: &str| -> Vec<&str> {
+            Animal::ALL
+                .into_iter()
+                .filter(|&animal| matches(animal, filter))
+                .map(|animal| animal.key())
+                .collect()
+        };
+        assert_eq!(keys(" GECKO"), ["leopard_gecko"]);
+        assert_eq!(keys("budgie"), ["parakeet"]);
+        assert_eq!(keys("horses"), ["horse", "pony", "donkey", "mule"]);
+        assert!(keys("fish").contains(&"betta"));
+
+        let text = render_filtered(Format::Text, Some("guinea"));
+        assert_eq!(
+            text,
+            "Available animals:\n\nRodents (-t rodents):\n\n  guinea_pig - Guinea pig / cavy\n"
+        );
+        assert_eq!(
+            render_filtered(Format::Text, Some("zebra")),
+            "No animals match 'zebra'.\n"
+        );
+        assert_eq!(
+            render_filtered(Format::Csv, Some("kitten")).lines().count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_other_formats_are_refused() {
+        let mut out = Vec::new();
+        assert!(print(Format::Yaml, None, false, &mut out).is_err());
+        assert!(out.is_empty());
+    }
+}
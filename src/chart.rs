@@ -0,0 +1,137 @@
+//! PNG aging-curve charts for `--chart`: each animal's human-years curve with its
+//! current age marked.
+
+use crate::{AppError, Conversion};
+use plotters::prelude::*;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Points sampled along each aging curve.
+const SAMPLES: usize = 200;
+
+/// Chart dimensions in pixels, written `WIDTHxHEIGHT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for ChartSize {
+    fn default() -> Self {
+        ChartSize {
+            width: 800,
+            height: 600,
+        }
+    }
+}
+
+impl FromStr for ChartSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected WIDTHxHEIGHT between 200 and 4000, got `{}`", s);
+        let (width, height) = s.split_once(['x', 'X']).ok_or_else(invalid)?;
+        let dimension = |d: &str| {
+            d.trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|d| (200..=4000).contains(d))
+                .ok_or_else(invalid)
+        };
+        Ok(ChartSize {
+            width: dimension(width)?,
+            height: dimension(height)?,
+        })
+    }
+}
+
+impl fmt::Display for ChartSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+fn chart_error(err: impl fmt::Display) -> AppError {
+    AppError::Chart(err.to_string())
+}
+
+/// Draws every conversion's aging curve, up to its lifespan (or current age, if
+/// older), and marks where the animal is now.
+pub fn write_png(conversions: &[Conversion], path: &Path, size: ChartSize) -> Result<(), AppError> {
+    let span = |c: &Conversion| c.animal_max.max(c.age);
+    let x_max = conversions.iter().map(span).fold(1.0, f32::max);
+    let y_max = conversions
+        .iter()
+        .map(|c| c.human_years(span(c)).max(c.human_age))
+        .fold(1.0, f32::max);
+
+    let root = BitMapBackend::new(path, (size.width, size.height)).into_drawing_area();
+    root.fill(&WHITE).map_err(chart_error)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Aging curves", ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0f32..x_max * 1.05, 0f32..y_max * 1.1)
+        .map_err(chart_error)?;
+    chart
+        .configure_mesh()
+        .x_desc("Age (years)")
+        .y_desc("Human years")
+        .draw()
+        .map_err(chart_error)?;
+
+    for (idx, conversion) in conversions.iter().enumerate() {
+        let color = Palette99::pick(idx).to_rgba();
+        let end = span(conversion);
+        let curve = (0..=SAMPLES).map(|i| {
+            let age = end * i as f32 / SAMPLES as f32;
+            (age, conversion.human_years(age))
+        });
+        chart
+            .draw_series(LineSeries::new(curve, color.stroke_width(2)))
+            .map_err(chart_error)?
+            .label(format!(
+                "{} ({})",
+                conversion.key(),
+                conversion.life_stage().key()
+            ))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        chart
+            .draw_series(std::iter::once(Circle::new(
+                (conversion.age, conversion.human_age),
+                6,
+                color.filled(),
+            )))
+            .map_err(chart_error)?;
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(chart_error)?;
+    root.present().map_err(chart_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chart_size() {
+        assert_eq!(
+            "1024x768".parse::<ChartSize>().unwrap(),
+            ChartSize {
+                width: 1024,
+                height: 768
+            }
+        );
+        assert!("1024".parse::<ChartSize>().is_err());
+        assert!("10x10".parse::<ChartSize>().is_err());
+        assert_eq!(ChartSize::default().to_string(), "800x600");
+    }
+}
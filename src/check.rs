@@ -0,0 +1,195 @@
+//! Condition expressions for `--check`, e.g. `human_age >= 65 && animal_progress < 0.9`.
+
+use crate::output::Output;
+use crate::{AppError, Conversion};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Age,
+    HumanAge,
+    HumanAgeLow,
+    HumanAgeHigh,
+    AnimalTypicalLifespan,
+    AnimalMaxLifespan,
+    HumanMaxLifespan,
+    AnimalProgress,
+    HumanProgress,
+    AgePercentile,
+    AgingRate,
+}
+
+impl Field {
+    /// Field names mirror the JSON output keys.
+    const NAMES: [(&'static str, Field); 11] = [
+        ("age", Field::Age),
+        ("human_age", Field::HumanAge),
+        ("human_age_low", Field::HumanAgeLow),
+        ("human_age_high", Field::HumanAgeHigh),
+        ("animal_typical_lifespan", Field::AnimalTypicalLifespan),
+        ("animal_max_lifespan", Field::AnimalMaxLifespan),
+        ("human_max_lifespan", Field::HumanMaxLifespan),
+        ("animal_progress", Field::AnimalProgress),
+        ("human_progress", Field::HumanProgress),
+        ("age_percentile", Field::AgePercentile),
+        ("aging_rate", Field::AgingRate),
+    ];
+
+    fn value(self, output: &Output) -> f32 {
+        match self {
+            Field::Age => output.age,
+            Field::HumanAge => output.human_age,
+            Field::HumanAgeLow => output.human_age_low,
+            Field::HumanAgeHigh => output.human_age_high,
+            Field::AnimalTypicalLifespan => output.animal_typical_lifespan,
+            Field::AnimalMaxLifespan => output.animal_max_lifespan,
+            Field::HumanMaxLifespan => output.human_max_lifespan,
+            Field::AnimalProgress => output.animal_progress,
+            Field::HumanProgress => output.human_progress,
+            Field::AgePercentile => output.age_percentile,
+            Field::AgingRate => output.aging_rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    // Two-character operators first so `>=` isn't read as `>`.
+    const SYMBOLS: [(&'static str, Op); 6] = [
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        ("==", Op::Eq),
+        ("!=", Op::Ne),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+    ];
+
+    fn apply(self, lhs: f32, rhs: f32) -> bool {
+        match self {
+            Op::Lt => lhs < rhs,
+            Op::Le => lhs <= rhs,
+            Op::Gt => lhs > rhs,
+            Op::Ge => lhs >= rhs,
+            Op::Eq => lhs == rhs,
+            Op::Ne => lhs != rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Comparison {
+    field: Field,
+    op: Op,
+    value: f32,
+}
+
+impl FromStr for Comparison {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| AppError::InvalidCheck(format!("`{}`: {}", s.trim(), reason));
+
+        let (idx, symbol, op) = Op::SYMBOLS
+            .iter()
+            .filter_map(|&(symbol, op)| s.find(symbol).map(|idx| (idx, symbol, op)))
+            .min_by_key(|&(idx, symbol, _)| (idx, usize::MAX - symbol.len()))
+            .ok_or_else(|| invalid("expected a comparison like `human_age >= 65`"))?;
+
+        let name = s[..idx].trim();
+        let field = Field::NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, field)| field)
+            .ok_or_else(|| invalid(&format!("unknown field `{}`", name)))?;
+        let value = s[idx + symbol.len()..]
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| invalid("right-hand side must be a number"))?;
+
+        Ok(Comparison { field, op, value })
+    }
+}
+
+/// A `--check` expression: comparisons joined by `&&`, alternatives joined by `||`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    any_of: Vec<Vec<Comparison>>,
+}
+
+impl FromStr for Check {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let any_of = s
+            .split("||")
+            .map(|alternative| alternative.split("&&").map(str::parse).collect())
+            .collect::<Result<_, _>>()?;
+        Ok(Check { any_of })
+    }
+}
+
+impl Check {
+    pub fn matches(&self, conversion: &Conversion) -> bool {
+        let output = Output::from(conversion);
+        self.any_of.iter().any(|all_of| {
+            all_of
+                .iter()
+                .all(|c| c.op.apply(c.field.value(&output), c.value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    #[test]
+    fn test_parse_check() {
+        let check: Check = "human_age >= 65".parse().unwrap();
+        assert_eq!(
+            check.any_of,
+            vec![vec![Comparison {
+                field: Field::HumanAge,
+                op: Op::Ge,
+                value: 65.0
+            }]]
+        );
+        assert!("weight > 3".parse::<Check>().is_err());
+        assert!("human_age >= old".parse::<Check>().is_err());
+        assert!("human_age".parse::<Check>().is_err());
+    }
+
+    #[test]
+    fn test_check_matches() {
+        let cat = convert("cat", 3.0).unwrap();
+        assert!("human_age == 29".parse::<Check>().unwrap().matches(&cat));
+        assert!(!"human_age > 29".parse::<Check>().unwrap().matches(&cat));
+        assert!("age_percentile == 50"
+            .parse::<Check>()
+            .unwrap()
+            .matches(&convert("cat", 14.0).unwrap()));
+        // A 10% band around 29 human years.
+        assert!("human_age_low == 26.1 && human_age_high == 31.9"
+            .parse::<Check>()
+            .unwrap()
+            .matches(&cat));
+        assert!("age < 2 || animal_progress < 0.25"
+            .parse::<Check>()
+            .unwrap()
+            .matches(&cat));
+        assert!(!"age > 2 && human_age < 20"
+            .parse::<Check>()
+            .unwrap()
+            .matches(&cat));
+    }
+}
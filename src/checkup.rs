@@ -0,0 +1,83 @@
+//! How often an animal should see a vet for a wellness exam at each life stage.
+//! The table lives in `data/` and is embedded at compile time.
+
+use crate::animal::{Animal, LifeStage};
+use std::sync::OnceLock;
+
+const CHECKUPS: &str = include_str!("../data/checkups.csv");
+
+/// Recommended months between wellness exams for one species, by life stage.
+#[derive(Debug, Clone, PartialEq)]
+struct Cadence {
+    /// An animal key, or `dog` for every dog size class.
+    animal: &'static str,
+    months: [u32; 6],
+}
+
+fn cadences() -> &'static [Cadence] {
+    static CADENCES: OnceLock<Vec<Cadence>> = OnceLock::new();
+    CADENCES.get_or_init(|| parse_table(CHECKUPS).collect())
+}
+
+/// Recommended months between wellness exams for `animal` in `stage`, if the
+/// table covers the species.
+pub fn checkup_months(animal: Animal, stage: LifeStage) -> Option<u32> {
+    let key = if animal.is_dog() { "dog" } else { animal.key() };
+    cadences()
+        .iter()
+        .find(|cadence| cadence.animal == key)
+        .map(|cadence| cadence.months[stage as usize])
+}
+
+/// Parses the embedded `animal,baby,…,geriatric` table. The data ships with
+/// the binary, so malformed rows are a bug rather than user error.
+fn parse_table(data: &'static str) -> impl Iterator<Item = Cadence> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [animal, ref months @ ..] = fields[..] else {
+                panic!("malformed checkup row: {}", line);
+            };
+            let months: Vec<u32> = months
+                .iter()
+                .map(|months| {
+                    months
+                        .parse()
+                        .unwrap_or_else(|_| panic!("bad months in row: {}", line))
+                })
+                .collect();
+            Cadence {
+                animal,
+                months: months
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("wrong number of stages in row: {}", line)),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkup_table_is_well_formed() {
+        for cadence in cadences() {
+            assert!(
+                cadence.animal == "dog" || Animal::from_key(cadence.animal).is_some(),
+                "unknown animal {}",
+                cadence.animal
+            );
+            assert!(cadence.months.iter().all(|&months| months > 0));
+        }
+    }
+
+    #[test]
+    fn test_checkup_months() {
+        assert_eq!(checkup_months(Animal::BigDog, LifeStage::Senior), Some(6));
+        assert_eq!(checkup_months(Animal::Cat, LifeStage::Adult), Some(12));
+        assert_eq!(checkup_months(Animal::Cat, LifeStage::Baby), Some(1));
+        assert_eq!(checkup_months(Animal::Goldfish, LifeStage::Adult), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! The user config file (`config.toml` in the platform config directory).
+
+use crate::bar::Thresholds;
+use crate::theme::Theme;
+use crate::AppError;
+use animal_age::animal::Formula;
+use animal_age::date::{Date, DAYS_PER_YEAR};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Table};
+
+/// Environment variable that overrides the config file location.
+pub const CONFIG_ENV: &str = "ANIMAL_AGE_CONFIG";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Saved pets, keyed by the name passed to `--profile`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Record every conversion in the history log (see `animal-age history`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub history: bool,
+    /// Character for the filled part of progress bars (`--bar-char`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bar_char: Option<char>,
+    /// Character for the empty part of progress bars (`--empty-char`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empty_char: Option<char>,
+    /// Percentages at which bars turn yellow and red (`--thresholds`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thresholds: Option<Thresholds>,
+    /// Largest edit distance for "did you mean" suggestions (`--suggest-distance`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggest_distance: Option<usize>,
+    /// Color theme used unless `--theme` picks another.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Custom themes, keyed by the name passed to `--theme`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, Theme>,
+    /// Standard used for each species unless `--standard` picks another, keyed
+    /// by animal type or `dog` for every dog.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub default_standards: BTreeMap<String, String>,
+    /// Custom standards, keyed by the name passed to `--standard`, each with a
+    /// formula per animal type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub standards: BTreeMap<String, BTreeMap<String, Formula>>,
+    /// Ages at which life stages start, keyed by animal type or `dog` for every
+    /// dog, then by stage; they replace the built-in thresholds.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub life_stages: BTreeMap<String, BTreeMap<String, f32>>,
+}
+
+/// A saved pet. The date of birth is stored so the profile ages with the pet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub animal: String,
+    pub dob: Date,
+}
+
+impl Profile {
+    /// Creates a profile for a pet that is `age` years old today.
+    pub fn from_age(animal: &str, age: f32) -> Profile {
+        let days = (age * DAYS_PER_YEAR).round() as i64;
+        Profile {
+            animal: animal.to_string(),
+            dob: Date::from_days(Date::today().days() - days),
+        }
+    }
+
+    /// The pet's age in years today, to the nearest tenth of a year.
+    pub fn age(&self) -> f32 {
+        (self.dob.years_until(Date::today()).max(0.0) * 10.0).round() / 10.0
+    }
+}
+
+/// Location of the config file, honoring `ANIMAL_AGE_CONFIG`.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    dirs::config_dir().map(|dir| dir.join("animal-age").join("config.toml"))
+}
+
+impl Config {
+    /// Loads the config file, falling back to defaults when it does not exist.
+    pub fn load() -> Result<Config, AppError> {
+        let Some(path) = path() else {
+            return Ok(Config::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| e.to_string())
+                .and_then(|config: Config| config.validate().map(|()| config))
+                .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes the config file, creating its directory if needed.
+    pub fn save(&self) -> Result<PathBuf, AppError> {
+        let path = path().ok_or_else(|| {
+            AppError::Config("could not determine the config directory".to_string())
+        })?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| AppError::Config(e.to_string()))?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Writes `formula` to `[standards.STANDARD.ANIMAL]` in the config file and
+    /// leaves the rest of the file, comments included, as it was.
+    pub fn save_formula(
+        standard: &str,
+        animal: &str,
+        formula: Formula,
+    ) -> Result<PathBuf, AppError> {
+        let path = path().ok_or_else(|| {
+            AppError::Config("could not determine the config directory".to_string())
+        })?;
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let contents = with_formula(&contents, standard, animal, formula)
+            .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Checks the custom standards' formulas, naming the first bad one.
+    fn validate(&self) -> Result<(), String> {
+        for (standard, formulas) in &self.standards {
+            for (animal, formula) in formulas {
+                formula
+                    .validate()
+                    .map_err(|e| format!("standards.{}.{}: {}", standard, animal, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&Profile, AppError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| AppError::Config(format!("no saved profile named '{}'", name)))
+    }
+}
+
+/// `contents` with `formula` set as `[standards.STANDARD.ANIMAL]`, replacing
+/// any formula already there.
+fn with_formula(
+    contents: &str,
+    standard: &str,
+    animal: &str,
+    formula: Formula,
+) -> Result<String, String> {
+    let mut document: DocumentMut = contents.parse().map_err(|e| format!("{}", e))?;
+    let parameters: DocumentMut = toml::to_string(&formula)
+        .map_err(|e| e.to_string())?
+        .parse()
+        .map_err(|e| format!("{}", e))?;
+    let mut table = Table::new();
+    for (key, value) in parameters.iter() {
+        table.insert(key, value.clone());
+    }
+    let standards = sub_table(document.as_table_mut(), "standards")?;
+    sub_table(standards, standard)?.insert(animal, Item::Table(table));
+    Ok(document.to_string())
+}
+
+/// The table under `key`, created as an implicit table (one with no header of its
+/// own) when it does not exist yet.
+fn sub_table<'a>(parent: &'a mut Table, key: &str) -> Result<&'a mut Table, String> {
+    parent
+        .entry(key)
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| format!("`{}` must be a table", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_round_trip() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "whiskers".to_string(),
+            Profile {
+                animal: "cat".to_string(),
+                dob: "2021-06-01".parse().unwrap(),
+            },
+        );
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(toml.contains("[profiles.whiskers]"));
+        assert!(toml.contains("dob = \"2021-06-01\""));
+
+        let parsed: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.profiles["whiskers"].animal, "cat");
+    }
+
+    #[test]
+    fn test_with_formula_keeps_the_rest() {
+        let contents = "\
+# My settings
+bar_char = \"#\"  # hashes
+unknown_key = 1
+
+[themes.ocean]
+low = \"red\"
+
+[standards.clinic.rabbit]
+model = \"linear\"
+offset = 0.0
+rate = 10.0
+";
+        let formula = Formula::Linear {
+            offset: 1.0,
+            rate: 5.0,
+        };
+        let saved = with_formula(contents, "calibrated", "axolotl", formula).unwrap();
+        assert!(saved.starts_with(contents));
+        assert!(saved.ends_with(
+            "\n[standards.calibrated.axolotl]\nmodel = \"linear\"\noffset = 1.0\nrate = 5.0\n"
+        ));
+        let config: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(config.standards["calibrated"]["axolotl"], formula);
+        assert!(config.standards["clinic"].contains_key("rabbit"));
+
+        // Saving again replaces the formula in place.
+        let formula = Formula::Linear {
+            offset: 2.0,
+            rate: 6.0,
+        };
+        let resaved = with_formula(&saved, "calibrated", "axolotl", formula).unwrap();
+        assert_eq!(resaved.matches("[standards.calibrated.axolotl]").count(), 1);
+        assert!(resaved.contains("rate = 6.0"));
+        assert!(with_formula("standards = 3", "calibrated", "cat", formula).is_err());
+    }
+
+    #[test]
+    fn test_validate_standards() {
+        let config: Config = toml::from_str(
+            "[standards.clinic.rabbit]\nmodel = \"linear\"\noffset = 0.0\nrate = 10.0\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let config: Config = toml::from_str(
+            "[standards.clinic.rabbit]\nmodel = \"linear\"\noffset = 0.0\nrate = -1.0\n",
+        )
+        .unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.starts_with("standards.clinic.rabbit: rate"), "{}", err);
+    }
+
+    #[test]
+    fn test_profile_from_age() {
+        let profile = Profile::from_age("cat", 3.0);
+        assert!((profile.age() - 3.0).abs() < 0.01);
+    }
+}
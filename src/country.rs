@@ -0,0 +1,83 @@
+//! Human life expectancy by country, for measuring human ages against a local
+//! lifespan instead of one global figure. The table lives in `data/` and is
+//! embedded at compile time.
+
+use std::sync::OnceLock;
+
+const LIFE_EXPECTANCY: &str = include_str!("../data/life_expectancy.csv");
+
+/// A country and how long people there live on average.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Country {
+    /// ISO 3166-1 alpha-2 code, e.g. `JP`.
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Life expectancy at birth in years.
+    pub life_expectancy: f32,
+}
+
+impl Country {
+    /// Every country in the table, sorted by code.
+    pub fn all() -> &'static [Country] {
+        static COUNTRIES: OnceLock<Vec<Country>> = OnceLock::new();
+        COUNTRIES.get_or_init(|| {
+            let mut countries: Vec<Country> = parse_table(LIFE_EXPECTANCY).collect();
+            countries.sort_by_key(|country| country.code);
+            countries
+        })
+    }
+
+    /// Looks up a country by code or name, ignoring case (`jp`, `"United States"`).
+    pub fn find(name: &str) -> Option<&'static Country> {
+        let wanted = name.trim();
+        Country::all().iter().find(|country| {
+            country.code.eq_ignore_ascii_case(wanted) || country.name.eq_ignore_ascii_case(wanted)
+        })
+    }
+}
+
+/// Parses the embedded `code,name,life_expectancy` table. The data ships with
+/// the binary, so malformed rows are a bug rather than user error.
+fn parse_table(data: &'static str) -> impl Iterator<Item = Country> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [code, name, years] = fields[..] else {
+                panic!("malformed life expectancy row: {}", line);
+            };
+            Country {
+                code,
+                name,
+                life_expectancy: years
+                    .parse()
+                    .unwrap_or_else(|_| panic!("bad life expectancy in row: {}", line)),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_life_expectancy_table_is_well_formed() {
+        let countries = Country::all();
+        assert!(countries.len() >= 40);
+        for pair in countries.windows(2) {
+            assert!(pair[0].code < pair[1].code, "duplicate {}", pair[1].code);
+        }
+        for country in countries {
+            assert_eq!(country.code.len(), 2);
+            assert!((40.0..100.0).contains(&country.life_expectancy));
+        }
+    }
+
+    #[test]
+    fn test_find_country() {
+        assert_eq!(Country::find("jp").unwrap().life_expectancy, 84.4);
+        assert_eq!(Country::find("United States").unwrap().code, "US");
+        assert!(Country::find("Atlantis").is_none());
+    }
+}
@@ -0,0 +1,246 @@
+//! Text line charts of human-equivalent age against animal age, for `--curve`.
+//! Several animals share one chart, each drawn with its own marker and its
+//! species color.
+
+use crate::bar::Bars;
+use crate::theme::{species_paint, Paint};
+use crate::Conversion;
+use std::io::{self, Write};
+
+/// Default plot width in columns, not counting the axis.
+const CURVE_WIDTH: usize = 50;
+/// Plot height in rows.
+const CURVE_HEIGHT: usize = 12;
+/// Markers for the curves, one per animal in turn.
+const CURVE_MARKS: [char; 6] = ['*', '+', 'x', '#', '%', '@'];
+const AGE_MARK: char = 'O';
+
+/// One character of the plot and the color of the curve it belongs to.
+#[derive(Clone, Copy)]
+struct Cell {
+    c: char,
+    paint: Paint,
+}
+
+/// A grid of characters with human years up the side and animal years along
+/// the bottom.
+struct Plot {
+    width: usize,
+    x_max: f32,
+    y_max: f32,
+    rows: Vec<Vec<Option<Cell>>>,
+}
+
+impl Plot {
+    fn new(width: usize, x_max: f32, y_max: f32) -> Plot {
+        Plot {
+            width,
+            x_max,
+            y_max,
+            rows: vec![vec![None; width]; CURVE_HEIGHT],
+        }
+    }
+
+    fn column(&self, x: f32) -> usize {
+        ((x / self.x_max * (self.width - 1) as f32).round() as usize).min(self.width - 1)
+    }
+
+    /// Row counted from the bottom.
+    fn row(&self, y: f32) -> usize {
+        ((y / self.y_max * (CURVE_HEIGHT - 1) as f32).round() as usize).min(CURVE_HEIGHT - 1)
+    }
+
+    fn set(&mut self, column: usize, row: usize, cell: Cell) {
+        self.rows[CURVE_HEIGHT - 1 - row][column] = Some(cell);
+    }
+
+    /// Draws `f` from 0 to `x_end`, filling the rows between neighbouring
+    /// columns so steep stretches stay connected.
+    fn curve(&mut self, f: impl Fn(f32) -> f32, x_end: f32, cell: Cell) {
+        let mut previous: Option<usize> = None;
+        for column in 0..=self.column(x_end) {
+            let x = column as f32 / (self.width - 1) as f32 * self.x_max;
+            let row = self.row(f(x));
+            let from = previous.map_or(row, |p| if p < row { p + 1 } else { row });
+            for r in from..=row {
+                self.set(column, r, cell);
+            }
+            previous = Some(row);
+        }
+    }
+
+    fn write(&self, color: bool, out: &mut dyn Write) -> io::Result<()> {
+        for (i, row) in self.rows.iter().enumerate() {
+            let from_bottom = CURVE_HEIGHT - 1 - i;
+            let label = if from_bottom % 3 == 2 || from_bottom == CURVE_HEIGHT - 1 {
+                format!(
+                    "{:>5.0}",
+                    from_bottom as f32 / (CURVE_HEIGHT - 1) as f32 * self.y_max
+                )
+            } else {
+                " ".repeat(5)
+            };
+            let end = row.iter().rposition(Option::is_some).map_or(0, |i| i + 1);
+            let line: String = row[..end]
+                .iter()
+                .map(|cell| match cell {
+                    Some(cell) => paint(cell.c, cell.paint, color),
+                    None => " ".to_string(),
+                })
+                .collect();
+            writeln!(out, "{} |{}", label, line)?;
+        }
+        writeln!(out, "{:>5} +{}", 0, "-".repeat(self.width))?;
+        let max = format!("{:.0}", self.x_max);
+        writeln!(
+            out,
+            "{:7}{}{}",
+            0,
+            " ".repeat(self.width.saturating_sub(max.len() + 1)),
+            max
+        )
+    }
+}
+
+/// `c` in `paint`, when color is on.
+fn paint(c: char, paint: Paint, color: bool) -> String {
+    match paint.escape() {
+        Some(escape) if color => format!("{}{}{}", escape, c, crate::color::RESET),
+        _ => c.to_string(),
+    }
+}
+
+/// Writes one chart with the aging curve of every conversion, each from birth to
+/// the end of its typical lifespan with the given age marked, and a legend. The
+/// chart is as wide as the bars when `--width` is given.
+pub fn write_curves(
+    conversions: &[Conversion],
+    bars: &Bars,
+    color: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let width = bars.width.unwrap_or(CURVE_WIDTH).max(2);
+    let end = |conversion: &Conversion| conversion.animal_max.max(conversion.age);
+    let x_max = conversions.iter().map(end).fold(0.0, f32::max);
+    let y_max = conversions
+        .iter()
+        .map(|conversion| conversion.human_years(end(conversion)))
+        .fold(0.0, f32::max);
+    if x_max <= 0.0 || y_max <= 0.0 {
+        return Ok(());
+    }
+
+    let mut plot = Plot::new(width, x_max, y_max);
+    for (series, conversion) in conversions.iter().enumerate() {
+        let animal = conversion.animal;
+        let c = CURVE_MARKS[series % CURVE_MARKS.len()];
+        plot.curve(
+            |age| conversion.human_years(age),
+            end(conversion),
+            Cell {
+                c,
+                paint: species_paint(animal),
+            },
+        );
+    }
+    // Age marks go on last so no curve hides them.
+    for conversion in conversions {
+        let (column, row) = (plot.column(conversion.age), plot.row(conversion.human_age));
+        plot.set(
+            column,
+            row,
+            Cell {
+                c: AGE_MARK,
+                paint: species_paint(conversion.animal),
+            },
+        );
+    }
+
+    match conversions {
+        [conversion] => writeln!(out, "Human years by {} age:\n", conversion.key())?,
+        _ => writeln!(out, "Human years by age:\n")?,
+    }
+    plot.write(color, out)?;
+    for (series, conversion) in conversions.iter().enumerate() {
+        writeln!(
+            out,
+            "{:7}{} {} years old {} {} = {:.*} human years",
+            "",
+            paint(
+                CURVE_MARKS[series % CURVE_MARKS.len()],
+                species_paint(conversion.animal),
+                color
+            ),
+            conversion.age,
+            conversion.key(),
+            AGE_MARK,
+            bars.decimals,
+            conversion.human_age
+        )?;
+    }
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    #[test]
+    fn test_curve_marks_the_age() {
+        let mut out = Vec::new();
+        let bars = Bars {
+            width: Some(19),
+            ..Bars::default()
+        };
+        write_curves(&[convert("cat", 9.0).unwrap()], &bars, false, &mut out).unwrap();
+        let chart = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+        // The top row is the curve's end at 18 years, 89 human years.
+        assert_eq!(lines[2], "   89 |                 **");
+        // Halfway along, at 9 years, the cat is 53 human years old.
+        let marked = lines.iter().find(|line| line.contains(AGE_MARK)).unwrap();
+        assert_eq!(marked.find(AGE_MARK), Some(7 + 9));
+        assert!(chart.contains("    0 +-------------------\n"));
+        assert!(chart.ends_with("* 9 years old cat O = 53.0 human years\n\n"));
+    }
+
+    #[test]
+    fn test_curve_snapshot() {
+        let mut out = Vec::new();
+        let bars = Bars {
+            width: Some(40),
+            ..Bars::default()
+        };
+        let pets = [
+            convert("cat", 9.0).unwrap(),
+            convert("rabbit", 4.0).unwrap(),
+        ];
+        write_curves(&pets, &bars, false, &mut out).unwrap();
+        insta::assert_snapshot!(String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn test_curves_share_one_chart() {
+        let pets = [
+            convert("hamster", 2.0).unwrap(),
+            convert("horse", 2.0).unwrap(),
+        ];
+        let mut out = Vec::new();
+        let bars = Bars {
+            width: Some(30),
+            ..Bars::default()
+        };
+        write_curves(&pets, &bars, false, &mut out).unwrap();
+        let chart = String::from_utf8(out).unwrap();
+        assert_eq!(chart.matches("Human years by").count(), 1);
+        // The hamster's curve stops at its 3-year lifespan, three columns in.
+        let hamster_columns = chart
+            .lines()
+            .filter_map(|line| line.rfind('*'))
+            .max()
+            .unwrap();
+        assert_eq!(hamster_columns, 7 + 3);
+        assert!(chart.contains("+ 2 years old horse O = 14.5 human years"));
+    }
+}
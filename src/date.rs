@@ -0,0 +1,186 @@
+//! Minimal calendar-date support for dates of birth.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Average length of a Gregorian year, used to turn day counts into ages.
+pub const DAYS_PER_YEAR: f32 = 365.25;
+
+/// A calendar date in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Error returned when a string is not a valid `YYYY-MM-DD` date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDate;
+
+impl fmt::Display for InvalidDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid date, expected YYYY-MM-DD")
+    }
+}
+
+impl std::error::Error for InvalidDate {}
+
+impl Date {
+    /// Today's date in UTC, according to the system clock.
+    pub fn today() -> Date {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Date::from_days((secs / 86_400) as i64)
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        let (m, d) = (i64::from(self.month), i64::from(self.day));
+        let y = i64::from(self.year) - i64::from(m <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The date `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year.clamp(0, 9999) as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// The date `years` whole years after `self`; February 29 falls back to
+    /// February 28 in common years.
+    pub fn anniversary(&self, years: u16) -> Date {
+        let year = self.year.saturating_add(years).min(9999);
+        Date {
+            year,
+            month: self.month,
+            day: self.day.min(days_in_month(year, self.month)),
+        }
+    }
+
+    /// Age in (fractional) years on `on` for something born on `self`.
+    pub fn years_until(&self, on: Date) -> f32 {
+        (on.days() - self.days()) as f32 / DAYS_PER_YEAR
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn parse_digits<T: FromStr>(digits: &str, len: usize) -> Result<T, InvalidDate> {
+    if digits.len() != len || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(InvalidDate);
+    }
+    digits.parse().map_err(|_| InvalidDate)
+}
+
+impl FromStr for Date {
+    type Err = InvalidDate;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(InvalidDate);
+        };
+
+        let year: u16 = parse_digits(year, 4)?;
+        let month: u8 = parse_digits(month, 2)?;
+        let day: u8 = parse_digits(day, 2)?;
+        if year == 0 || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(InvalidDate);
+        }
+
+        Ok(Date { year, month, day })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anniversary() {
+        let dob: Date = "2020-02-29".parse().unwrap();
+        assert_eq!(dob.anniversary(1).to_string(), "2021-02-28");
+        assert_eq!(dob.anniversary(4).to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn test_days_round_trip() {
+        let epoch = Date {
+            year: 1970,
+            month: 1,
+            day: 1,
+        };
+        assert_eq!(epoch.days(), 0);
+        for days in [-1, 0, 59, 365, 11_016, 20_000, 2_932_896] {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let date: Date = "2020-02-29".parse().unwrap();
+        assert_eq!(date.to_string(), "2020-02-29");
+        assert!("2021-02-29".parse::<Date>().is_err());
+        assert!("2021-13-01".parse::<Date>().is_err());
+        assert!("2021-1-01".parse::<Date>().is_err());
+        assert!("+021-01-01".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn test_years_until() {
+        let dob: Date = "2020-03-01".parse().unwrap();
+        let today: Date = "2023-03-01".parse().unwrap();
+        assert!((dob.years_until(today) - 3.0).abs() < 0.01);
+    }
+}
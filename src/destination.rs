@@ -0,0 +1,103 @@
+//! Where results are written: stdout, or an `--output` file that is replaced only
+//! once every result has been written.
+
+use crate::AppError;
+use anstream::AutoStream;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+pub enum Destination {
+    /// Color escapes pass straight through, except on legacy Windows consoles,
+    /// where they are translated to console API calls.
+    Stdout(AutoStream<io::StdoutLock<'static>>),
+    /// Results go to a temporary file next to `path`, renamed over it by
+    /// [`Destination::finish`]. A failed run leaves an existing `path` untouched.
+    File {
+        file: BufWriter<File>,
+        temp: PathBuf,
+        path: PathBuf,
+    },
+}
+
+impl Destination {
+    pub fn stdout() -> Self {
+        Destination::Stdout(AutoStream::always(io::stdout().lock()))
+    }
+
+    pub fn file(path: &Path) -> Result<Self, AppError> {
+        let name = path
+            .file_name()
+            .ok_or_else(|| AppError::InvalidInput(format!("{} is not a file", path.display())))?;
+        let temp_name = format!(".{}.{}.tmp", name.to_string_lossy(), process::id());
+        let temp = path.with_file_name(temp_name);
+        Ok(Destination::File {
+            file: BufWriter::new(File::create(&temp)?),
+            temp,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Flushes everything written and, for a file, moves it into place.
+    pub fn finish(mut self) -> Result<(), AppError> {
+        self.flush()?;
+        if let Destination::File { temp, path, .. } = &mut self {
+            fs::rename(&*temp, &*path)?;
+            // Renamed away, so there is nothing left for `drop` to clean up.
+            temp.clear();
+        }
+        Ok(())
+    }
+}
+
+impl Write for Destination {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Destination::Stdout(out) => out.write(buf),
+            Destination::File { file, .. } => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Destination::Stdout(out) => out.flush(),
+            Destination::File { file, .. } => file.flush(),
+        }
+    }
+}
+
+impl Drop for Destination {
+    fn drop(&mut self) {
+        if let Destination::File { temp, .. } = self {
+            if !temp.as_os_str().is_empty() {
+                let _ = fs::remove_file(temp);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_is_replaced_only_when_finished() {
+        let dir = std::env::temp_dir().join(format!("animal-age-dest-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("report.csv");
+        fs::write(&path, "old").unwrap();
+
+        let mut failed = Destination::file(&path).unwrap();
+        write!(failed, "partial").unwrap();
+        drop(failed);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        let mut done = Destination::file(&path).unwrap();
+        write!(done, "new").unwrap();
+        done.finish().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,254 @@
+//! The opt-in history log (`history = true` in the config file): every conversion
+//! is recorded in a local SQLite database, listed by `animal-age history`.
+
+use crate::config::{self, Config};
+use crate::{output, AppError, Conversion};
+use animal_age::date::Date;
+use rusqlite::{params, Connection};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable that overrides the history database location.
+pub const HISTORY_ENV: &str = "ANIMAL_AGE_HISTORY";
+
+#[derive(clap::Args)]
+pub struct HistoryArgs {
+    /// Only show conversions of this animal type or breed
+    #[arg(long, value_name = "ANIMAL")]
+    animal: Option<String>,
+
+    /// Only show conversions made with this --profile
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Only show conversions made on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    since: Option<Date>,
+
+    /// Most entries to show, newest first
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    limit: u32,
+}
+
+/// One recorded conversion.
+#[derive(Debug)]
+struct Entry {
+    recorded_at: i64,
+    profile: Option<String>,
+    animal: String,
+    age: f32,
+    human_age: f32,
+}
+
+/// Location of the history database, honoring `ANIMAL_AGE_HISTORY`.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(HISTORY_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    dirs::data_dir().map(|dir| dir.join("animal-age").join("history.sqlite3"))
+}
+
+fn history_error(err: rusqlite::Error) -> AppError {
+    AppError::History(err.to_string())
+}
+
+fn open() -> Result<Connection, AppError> {
+    let path = path()
+        .ok_or_else(|| AppError::History("could not determine the data directory".to_string()))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let conn = Connection::open(&path).map_err(history_error)?;
+    create_table(&conn)?;
+    Ok(conn)
+}
+
+fn create_table(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS conversions (
+            id INTEGER PRIMARY KEY,
+            recorded_at INTEGER NOT NULL,
+            profile TEXT,
+            animal TEXT NOT NULL,
+            age REAL NOT NULL,
+            human_age REAL NOT NULL,
+            animal_max_lifespan REAL NOT NULL
+        )",
+    )
+    .map_err(history_error)
+}
+
+/// Whether the config file turns the history log on. A config file that cannot be
+/// read leaves it off rather than failing the conversion.
+pub fn enabled() -> bool {
+    Config::load().is_ok_and(|config| config.history)
+}
+
+/// Appends `conversions` to the history log, tagged with the profile they came from.
+pub fn record(conversions: &[Conversion], profile: Option<&str>) -> Result<(), AppError> {
+    insert(&mut open()?, conversions, profile, unix_now())
+}
+
+fn insert(
+    conn: &mut Connection,
+    conversions: &[Conversion],
+    profile: Option<&str>,
+    recorded_at: i64,
+) -> Result<(), AppError> {
+    let tx = conn.transaction().map_err(history_error)?;
+    for conversion in conversions {
+        tx.execute(
+            "INSERT INTO conversions
+                (recorded_at, profile, animal, age, human_age, animal_max_lifespan)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                recorded_at,
+                profile,
+                conversion.key(),
+                conversion.age,
+                conversion.human_age,
+                conversion.animal_max
+            ],
+        )
+        .map_err(history_error)?;
+    }
+    tx.commit().map_err(history_error)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+fn query(conn: &Connection, args: &HistoryArgs) -> Result<Vec<Entry>, AppError> {
+    let since = args.since.map(|date| date.days() * 86_400);
+    let mut statement = conn
+        .prepare(
+            "SELECT recorded_at, profile, animal, age, human_age FROM conversions
+             WHERE (?1 IS NULL OR animal = lower(?1))
+               AND (?2 IS NULL OR profile = ?2)
+               AND (?3 IS NULL OR recorded_at >= ?3)
+             ORDER BY recorded_at DESC, id DESC
+             LIMIT ?4",
+        )
+        .map_err(history_error)?;
+    let entries = statement
+        .query_map(
+            params![args.animal, args.profile, since, args.limit],
+            |row| {
+                Ok(Entry {
+                    recorded_at: row.get(0)?,
+                    profile: row.get(1)?,
+                    animal: row.get(2)?,
+                    age: row.get(3)?,
+                    human_age: row.get(4)?,
+                })
+            },
+        )
+        .map_err(history_error)?;
+    entries.collect::<Result<_, _>>().map_err(history_error)
+}
+
+/// `YYYY-MM-DD HH:MM` in UTC.
+fn format_timestamp(secs: i64) -> String {
+    let minutes = secs.rem_euclid(86_400) / 60;
+    format!(
+        "{} {:02}:{:02}",
+        Date::from_days(secs.div_euclid(86_400)),
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// Lists recorded conversions, newest first.
+pub fn run(args: &HistoryArgs) -> Result<(), AppError> {
+    let entries = query(&open()?, args)?;
+    let mut out = io::stdout().lock();
+    let filtered = args.animal.is_some() || args.profile.is_some() || args.since.is_some();
+    if entries.is_empty() && filtered {
+        writeln!(out, "No matching conversions.")?;
+        return Ok(());
+    }
+    if entries.is_empty() {
+        writeln!(out, "No conversions recorded yet.")?;
+        if !enabled() {
+            let config = config::path().map_or("the config file".to_string(), |path| {
+                path.display().to_string()
+            });
+            writeln!(
+                out,
+                "Turn the history log on with `history = true` in {}.",
+                config
+            )?;
+        }
+        return Ok(());
+    }
+
+    let mut table = output::new_table(
+        false,
+        ["When (UTC)", "Profile", "Animal", "Age", "Human age"],
+        &[3, 4],
+    );
+    for entry in entries {
+        table.add_row([
+            format_timestamp(entry.recorded_at),
+            entry.profile.unwrap_or_else(|| "-".to_string()),
+            entry.animal,
+            format!("{:.1}", entry.age),
+            format!("{:.1}", entry.human_age),
+        ]);
+    }
+    writeln!(out, "{}", table)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    fn filter(animal: Option<&str>, profile: Option<&str>, since: Option<&str>) -> HistoryArgs {
+        HistoryArgs {
+            animal: animal.map(str::to_string),
+            profile: profile.map(str::to_string),
+            since: since.map(|date| date.parse().unwrap()),
+            limit: 20,
+        }
+    }
+
+    #[test]
+    fn test_record_and_filter() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        create_table(&conn).unwrap();
+        let day = 86_400;
+        let cat = [convert("cat", 3.0).unwrap()];
+        insert(&mut conn, &cat, Some("whiskers"), 100 * day).unwrap();
+        insert(
+            &mut conn,
+            &[convert("horse", 5.0).unwrap()],
+            None,
+            200 * day,
+        )
+        .unwrap();
+        insert(&mut conn, &cat, Some("whiskers"), 300 * day).unwrap();
+
+        let all = query(&conn, &filter(None, None, None)).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].recorded_at, 300 * day);
+
+        let cats = query(&conn, &filter(Some("CAT"), Some("whiskers"), None)).unwrap();
+        assert_eq!(cats.len(), 2);
+        assert_eq!(cats[0].human_age, 29.0);
+
+        let recent = query(&conn, &filter(None, None, Some("1970-09-01"))).unwrap();
+        assert_eq!(recent.len(), 1);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(86_400 + 3_723), "1970-01-02 01:02");
+    }
+}
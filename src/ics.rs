@@ -0,0 +1,237 @@
+//! iCalendar export of a pet's upcoming milestones, for `--format ics`: its next
+//! birthdays and the dates it becomes an adult and a senior, as all-day events.
+
+use crate::{AppError, Conversion};
+use animal_age::animal::SENIOR_SHARE;
+use animal_age::date::{Date, DAYS_PER_YEAR};
+use std::io::{self, Write};
+
+/// Longest content line allowed before folding, in octets (RFC 5545 §3.1).
+const LINE_LIMIT: usize = 75;
+
+/// One all-day calendar event.
+#[derive(Debug)]
+struct Event {
+    date: Date,
+    kind: String,
+    summary: String,
+}
+
+/// The date `years` (possibly fractional) after `dob`.
+fn date_after(dob: Date, years: f32) -> Date {
+    Date::from_days(dob.days() + (years * DAYS_PER_YEAR).round() as i64)
+}
+
+/// Milestones of `conversion` after `today`: every birthday up to the end of its
+/// typical lifespan (at least the next one), plus the adult and senior transitions.
+fn events(conversion: &Conversion, dob: Date, today: Date) -> Vec<Event> {
+    let name = conversion.name.as_deref().unwrap_or(&conversion.label);
+    let mut next = 1;
+    while dob.anniversary(next) <= today && next < u16::MAX {
+        next += 1;
+    }
+    let last = next.max(conversion.animal_max.ceil() as u16);
+
+    let mut events: Vec<Event> = (next..=last)
+        .map(|years| Event {
+            date: dob.anniversary(years),
+            kind: format!("birthday-{}", years),
+            summary: format!(
+                "{} turns {} (≈ {:.0} human years)",
+                name,
+                years,
+                conversion.human_years(years as f32)
+            ),
+        })
+        .collect();
+
+    let stages = [
+        ("adult", "an adult", conversion.animal.adult_age()),
+        ("senior", "a senior", conversion.animal_max * SENIOR_SHARE),
+    ];
+    for (kind, stage, years) in stages {
+        let date = date_after(dob, years);
+        if date > today {
+            events.push(Event {
+                date,
+                kind: kind.to_string(),
+                summary: format!("{} becomes {}", name, stage),
+            });
+        }
+    }
+    events.sort_by_key(|event| event.date);
+    events
+}
+
+/// Escapes TEXT values: backslashes, semicolons, commas, and newlines.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes one content line, folded onto continuation lines that start with a
+/// space so that no line exceeds [`LINE_LIMIT`] octets.
+fn write_line(out: &mut dyn Write, line: &str) -> io::Result<()> {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.write_all(b"\r\n ")?;
+            width = 1;
+        }
+        write!(out, "{}", c)?;
+        width += c.len_utf8();
+    }
+    out.write_all(b"\r\n")
+}
+
+/// `YYYYMMDD`, the iCalendar DATE form.
+fn ics_date(date: Date) -> String {
+    format!("{:04}{:02}{:02}", date.year, date.month, date.day)
+}
+
+/// Lowercase letters and digits of `text`, with hyphens for everything else.
+fn slug(text: &str) -> String {
+    let slug: String = text
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+/// Writes a calendar of upcoming milestones for every conversion, which must all
+/// carry a date of birth.
+pub fn write_calendar(conversions: &[Conversion], out: &mut dyn Write) -> Result<(), AppError> {
+    write_calendar_on(conversions, Date::today(), out)
+}
+
+fn write_calendar_on(
+    conversions: &[Conversion],
+    today: Date,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    conversions.iter().try_for_each(check_dob)?;
+    write_start(out)?;
+    for conversion in conversions {
+        write_events(conversion, today, out)?;
+    }
+    write_end(out)?;
+    Ok(())
+}
+
+/// Fails unless `conversion` carries the date of birth its events are dated from.
+pub fn check_dob(conversion: &Conversion) -> Result<(), AppError> {
+    match conversion.dob {
+        Some(_) => Ok(()),
+        None => Err(AppError::InvalidInput(
+            "ics output needs a date of birth (--dob)".to_string(),
+        )),
+    }
+}
+
+/// Writes the lines that open a calendar, before any events.
+pub fn write_start(out: &mut dyn Write) -> io::Result<()> {
+    write_line(out, "BEGIN:VCALENDAR")?;
+    write_line(out, "VERSION:2.0")?;
+    write_line(
+        out,
+        &format!(
+            "PRODID:-//animal-age//animal-age {}//EN",
+            env!("CARGO_PKG_VERSION")
+        ),
+    )?;
+    write_line(out, "CALSCALE:GREGORIAN")
+}
+
+/// Writes the upcoming milestones of `conversion` as events; one without a date
+/// of birth has none.
+pub fn write_events(conversion: &Conversion, today: Date, out: &mut dyn Write) -> io::Result<()> {
+    let Some(dob) = conversion.dob else {
+        return Ok(());
+    };
+    let owner = slug(conversion.name.as_deref().unwrap_or(conversion.key()));
+    for event in events(conversion, dob, today) {
+        let end = Date::from_days(event.date.days() + 1);
+        write_line(out, "BEGIN:VEVENT")?;
+        write_line(
+            out,
+            &format!("UID:{}-{}-{}@animal-age", dob, event.kind, owner),
+        )?;
+        write_line(out, &format!("DTSTAMP:{}T000000Z", ics_date(today)))?;
+        write_line(out, &format!("DTSTART;VALUE=DATE:{}", ics_date(event.date)))?;
+        write_line(out, &format!("DTEND;VALUE=DATE:{}", ics_date(end)))?;
+        write_line(out, &format!("SUMMARY:{}", escape_text(&event.summary)))?;
+        write_line(out, "TRANSP:TRANSPARENT")?;
+        write_line(out, "END:VEVENT")?;
+    }
+    Ok(())
+}
+
+/// Writes the line that closes a calendar.
+pub fn write_end(out: &mut dyn Write) -> io::Result<()> {
+    write_line(out, "END:VCALENDAR")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    #[test]
+    fn test_calendar_lists_upcoming_milestones() {
+        let mut cat = convert("cat", 0.5).unwrap();
+        cat.name = Some("Whiskers, Jr.".to_string());
+        cat.dob = Some("2024-02-29".parse().unwrap());
+        let today = "2024-09-01".parse().unwrap();
+
+        let mut out = Vec::new();
+        write_calendar_on(&[cat], today, &mut out).unwrap();
+        let calendar = String::from_utf8(out).unwrap();
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        // 18 birthdays, the adult transition, and the senior one at 13.5 years.
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 20);
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20250228\r\n"));
+        assert!(calendar.contains("SUMMARY:Whiskers\\, Jr. turns 1 (≈ 12 human years)"));
+        assert!(calendar.contains("UID:2024-02-29-senior-whiskers--jr@animal-age"));
+        assert!(calendar.lines().all(|line| line.len() <= LINE_LIMIT));
+    }
+
+    #[test]
+    fn test_old_pet_still_gets_next_birthday() {
+        let mut hamster = convert("hamster", 5.0).unwrap();
+        hamster.dob = Some("2020-03-01".parse().unwrap());
+        let events = events(
+            &hamster,
+            hamster.dob.unwrap(),
+            "2025-06-01".parse().unwrap(),
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].date.to_string(), "2026-03-01");
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let mut out = Vec::new();
+        write_line(&mut out, &"é".repeat(60)).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = text.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 74);
+        assert!(lines[1].starts_with(' '));
+    }
+}
@@ -0,0 +1,10 @@
+//! Core library behind the `animal-age` CLI: species data, age conversion, and
+//! hardened parsers for untrusted user input.
+
+pub mod animal;
+pub mod breed;
+pub mod checkup;
+pub mod country;
+pub mod date;
+pub mod parse;
+pub mod weight;
@@ -1,15 +1,92 @@
-use clap::Parser;
+use animal_age::animal::{
+    survival, Animal, Category, Formula, LifeStage, LifeStages, Precision, Sex, Standard,
+    CONTINUOUS_DOG_SOURCE, CONTINUOUS_DOG_STANDARD, FORMULA_SOURCE, MAX_AGE_YEARS,
+    MIXED_DOG_METHOD,
+};
+use animal_age::breed::Breed;
+use animal_age::checkup::checkup_months;
+use animal_age::country::Country;
+use animal_age::date::Date;
+use animal_age::parse;
+use animal_age::weight::Weight;
+use bar::{BarStyle, Bars, Thresholds};
+use check::Check;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use color::ColorChoice;
+use config::Config;
 use console::Term;
-use serde::Serialize;
+use destination::Destination;
+use output::{Format, SortKey};
+use stage::Stages;
+use standard::Standards;
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::time::Duration;
 use strsim::levenshtein;
+use theme::Theme;
 use thiserror::Error;
+use unicode_width::UnicodeWidthStr;
+use vertical::Column;
+
+mod art;
+mod bar;
+mod batch;
+mod calibrate;
+mod catalog;
+#[cfg(feature = "chart")]
+mod chart;
+mod check;
+mod config;
+mod curve;
+mod destination;
+#[cfg(feature = "history")]
+mod history;
+mod ics;
+mod onboarding;
+mod output;
+#[cfg(feature = "parquet")]
+mod parquet_file;
+#[cfg(feature = "parse")]
+mod parse_cmd;
+mod pdf;
+mod schema;
+mod stage;
+mod standard;
+mod svg;
+mod theme;
+mod vertical;
 
 mod color {
     pub const RESET: &str = "\x1b[0m";
-    pub const CYAN: &str = "\x1b[36m";
-    pub const YELLOW: &str = "\x1b[33m";
-    pub const RED: &str = "\x1b[31m";
+
+    /// Environment variable that turns color off when set to anything
+    /// (https://no-color.org).
+    pub const NO_COLOR_ENV: &str = "NO_COLOR";
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+    pub enum ColorChoice {
+        /// Color a terminal unless NO_COLOR is set
+        #[default]
+        Auto,
+        Always,
+        Never,
+    }
+
+    impl ColorChoice {
+        /// Whether to color output going to a terminal (`is_term`) or elsewhere.
+        pub fn enabled(self, is_term: bool) -> bool {
+            match self {
+                ColorChoice::Always => true,
+                ColorChoice::Never => false,
+                ColorChoice::Auto => {
+                    is_term && std::env::var_os(NO_COLOR_ENV).is_none_or(|v| v.is_empty())
+                }
+            }
+        }
+    }
 }
 
 /// CLI tool to convert animal years to human years and show lifespan progress.
@@ -22,11 +99,27 @@ mod color {
                   \tanimal-age -t cat -a 3\n\
                   \tanimal-age --type small_dog --age 5\n\
                   \tanimal-age --list\n\
-                  \tanimal-age -t horse -a 10 --json\n\
-                  \tanimal-age -t cat,small_dog -a 3 --no-color\n"
+                  \tanimal-age --weight 28kg -a 5\n\
+                  \tanimal-age --breed beagle -a 5\n\
+                  \tanimal-age info --breed beagle\n\
+                  \tanimal-age -t horse -a 10 --format json\n\
+                  \tanimal-age -t cat,small_dog -a 3 --color never\n\
+                  \tcat pets.txt | animal-age --stdin\n\
+                  \tanimal-age --input intake.csv --csv\n\
+                  \tanimal-age --input intake.csv --output handouts.pdf\n\
+                  \tanimal-age -t cat,big_dog -a 8 --output chart.svg\n\
+                  \tanimal-age -t cat,big_dog -a 8 --chart curves.png\n\
+                  \tanimal-age --profile whiskers\n\
+                  \tanimal-age completions bash\n\
+                  \tanimal-age -t cat -a 12 --check \"human_age >= 65\" && echo senior\n\
+                  \tAGE=$(animal-age -t cat -a 3 -q)\n"
 )]
 struct Args {
-    /// Animal type (use --list to show valid options, supports comma-separated list)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Animal type (use --list to show valid options, supports comma-separated list, a
+    /// group such as `rodents`, or `all` for every animal)
     #[arg(
         short = 't',
         long = "type",
@@ -35,407 +128,1652 @@ struct Args {
     )]
     animal: Option<Vec<String>>,
 
-    /// Age of the animal in real years
-    #[arg(short = 'a', long = "age", value_name = "YEARS")]
+    /// Age of the animal in real years, or with a unit such as 18mo, 6w, or 10d
+    #[arg(short = 'a', long = "age", value_name = "YEARS", value_parser = parse_years)]
     age: Option<f32>,
 
+    /// Date of birth (YYYY-MM-DD), instead of --age; needed for --format ics
+    #[arg(
+        long = "dob",
+        value_name = "DATE",
+        conflicts_with_all = ["age", "stdin", "input"]
+    )]
+    dob: Option<Date>,
+
+    /// Find the animal age equivalent to this many human years, instead of --age
+    #[arg(
+        long = "from-human",
+        value_name = "YEARS",
+        value_parser = parse_years,
+        conflicts_with_all = ["age", "dob", "stdin", "input"]
+    )]
+    from_human: Option<f32>,
+
+    /// Dog weight (e.g. 28kg or 60lb); interpolates the rates and lifespan between the dog sizes
+    #[arg(
+        short = 'w',
+        long = "weight",
+        value_name = "WEIGHT",
+        conflicts_with_all = ["animal", "list", "stdin", "input"]
+    )]
+    weight: Option<Weight>,
+
+    /// Dog or cat breed (e.g. beagle, maine_coon); picks the formula and typical lifespan
+    #[arg(
+        long = "breed",
+        value_name = "BREED",
+        conflicts_with_all = ["animal", "weight", "list", "stdin", "input"]
+    )]
+    breed: Option<String>,
+
+    /// Accepted for compatibility; --weight always interpolates by weight now
+    #[arg(long = "mixed", requires = "weight", hide = true)]
+    _mixed: bool,
+
+    /// The animal's sex, which adjusts the lifespan of cats and dogs
+    #[arg(long = "sex", value_name = "SEX", conflicts_with_all = ["stdin", "input"])]
+    sex: Option<Sex>,
+
+    /// The cat or dog is neutered or spayed, which lengthens its lifespan
+    #[arg(long = "neutered", visible_alias = "spayed", conflicts_with_all = ["stdin", "input"])]
+    neutered: bool,
+
+    /// Estimate the chance the animal lives to this age, from its survival curve
+    #[arg(long = "survive-to", value_name = "YEARS", value_parser = parse_years)]
+    survive_to: Option<f32>,
+
+    /// Don't recommend how often the animal should see a vet
+    #[arg(long = "no-checkup")]
+    no_checkup: bool,
+
+    /// Treat warnings, such as an age past the typical lifespan, as errors
+    #[arg(long = "strict", conflicts_with = "no_warn")]
+    strict: bool,
+
+    /// Don't print warnings, such as an age past the typical lifespan
+    #[arg(long = "no-warn")]
+    no_warn: bool,
+
+    /// Human lifespan in years that human ages are measured against (default 80)
+    #[arg(long = "human-max", value_name = "YEARS", conflicts_with = "country")]
+    human_max: Option<f32>,
+
+    /// Measure human ages against a country's life expectancy (e.g. JP or "United States")
+    #[arg(long = "country", value_name = "COUNTRY")]
+    country: Option<String>,
+
     /// Show supported animal types
     #[arg(long = "list")]
     list: bool,
 
-    /// Output in JSON format
-    #[arg(long = "json", help = "Output in JSON format")]
+    /// With --list, show only animals whose key, description, alias, or group contains TEXT
+    #[arg(long = "filter", value_name = "TEXT", requires = "list")]
+    filter: Option<String>,
+
+    /// Output format for results
+    #[arg(long = "format", value_name = "FORMAT", value_enum)]
+    format: Option<Format>,
+
+    /// Output in JSON format (alias for --format json)
+    #[arg(long = "json", conflicts_with = "format")]
     json: bool,
 
+    /// Output in CSV format (alias for --format csv)
+    #[arg(long = "csv", conflicts_with_all = ["format", "json"])]
+    csv: bool,
+
+    /// Print JSON on one line (the default when output is piped or written to a file)
+    #[arg(long = "compact")]
+    compact: bool,
+
     /// Disable colored output
-    #[arg(long = "no-color", help = "Disable colored output")]
+    #[arg(long = "no-color", hide = true, conflicts_with = "color")]
     no_color: bool,
+
+    /// When to color output: auto (only on a terminal, unless NO_COLOR is set), always, or never
+    #[arg(long = "color", value_name = "WHEN", value_enum, default_value_t)]
+    color: ColorChoice,
+
+    /// How to draw the progress bars
+    #[arg(long = "bar-style", value_name = "STYLE", value_enum, default_value_t)]
+    bar_style: BarStyle,
+
+    /// Character for the filled part of progress bars (e.g. '#' or '█')
+    #[arg(long = "bar-char", value_name = "CHAR")]
+    bar_char: Option<char>,
+
+    /// Character for the empty part of progress bars (e.g. '·'); a space by default
+    #[arg(long = "empty-char", value_name = "CHAR")]
+    empty_char: Option<char>,
+
+    /// Percentages of the lifespan at which bars turn yellow and red
+    #[arg(long = "thresholds", value_name = "YELLOW,RED")]
+    thresholds: Option<Thresholds>,
+
+    /// Largest Levenshtein distance at which a mistyped animal or breed gets a
+    /// suggestion; by default it grows with the length of the input
+    #[arg(long = "suggest-distance", value_name = "N")]
+    suggest_distance: Option<usize>,
+
+    /// Color theme: default, pastel, monochrome, solarized, colorblind, or one from the config file
+    #[arg(long = "theme", value_name = "NAME")]
+    theme: Option<String>,
+
+    /// Also plot human years against the animal's age, with its current age marked
+    #[arg(long = "curve")]
+    curve: bool,
+
+    /// Print an ASCII portrait of the species above its bars
+    #[arg(long = "art")]
+    art: bool,
+
+    /// Prefix each animal with its species emoji
+    #[arg(long = "emoji")]
+    emoji: bool,
+
+    /// Show the animal and its human equivalent as two markers on one shared bar
+    #[arg(long = "inline")]
+    inline: bool,
+
+    /// Draw upright bars side by side, one per animal
+    #[arg(long = "vertical", conflicts_with = "inline")]
+    vertical: bool,
+
+    /// Conversion standard: classic, epigenetic (dogs only), aaha (cats only), or one from the config file
+    #[arg(long = "standard", visible_alias = "formula", value_name = "NAME")]
+    standard: Option<String>,
+
+    /// Describe lifespans in words instead of bars, with no colors or box drawing
+    #[arg(long = "plain")]
+    plain: bool,
+
+    /// Decimal places of human years and percentages, 0 to 3
+    #[arg(long = "precision", value_name = "DIGITS", value_parser = clap::value_parser!(u8).range(0..=3))]
+    precision: Option<u8>,
+
+    /// Report human ages unrounded in JSON, CSV, and other machine-readable output
+    #[arg(long = "no-round", conflicts_with = "precision")]
+    no_round: bool,
+
+    /// Fill the bars over about a second (only when writing to a terminal)
+    #[arg(long = "animate")]
+    animate: bool,
+
+    /// Width of the progress bars in characters, instead of fitting the terminal
+    #[arg(long = "width", value_name = "COLUMNS", value_parser = clap::value_parser!(u16).range(1..))]
+    width: Option<u16>,
+
+    /// Order multiple animals by this value, highest first
+    #[arg(long = "sort", value_name = "KEY", value_enum, conflicts_with_all = ["stdin", "input"])]
+    sort: Option<SortKey>,
+
+    /// Draw borders around the multi-animal comparison table
+    #[arg(long = "borders")]
+    borders: bool,
+
+    /// Print only the human age, one line per animal
+    #[arg(
+        short = 'q',
+        long = "quiet",
+        conflicts_with_all = ["format", "json", "csv", "compact", "list", "stdin", "input", "check"]
+    )]
+    quiet: bool,
+
+    /// Read `animal,age` lines (or JSON objects, or one JSON array) from stdin
+    #[arg(long = "stdin", conflicts_with_all = ["animal", "age", "list"])]
+    stdin: bool,
+
+    /// Read pets from a CSV file (`name`, `species`, `age` or `dob` columns) or a JSON array
+    #[arg(
+        long = "input",
+        value_name = "FILE",
+        conflicts_with_all = ["animal", "age", "list", "stdin"]
+    )]
+    input: Option<PathBuf>,
+
+    /// Print nothing; exit 0 if the expression holds for every animal, 1 otherwise
+    #[arg(
+        long = "check",
+        value_name = "EXPR",
+        conflicts_with_all = ["list", "stdin", "input"]
+    )]
+    check: Option<Check>,
+
+    /// Use the animal and age of a profile saved in the config file
+    #[arg(long = "profile", value_name = "NAME", conflicts_with_all = ["stdin", "input"])]
+    profile: Option<String>,
+
+    /// Write results to FILE (`-` for stdout); a .pdf, .svg, .ics, or .parquet name selects that format
+    #[arg(long = "output", value_name = "FILE", conflicts_with_all = ["list", "check"])]
+    output: Option<PathBuf>,
+
+    /// Also draw each animal's aging curve and current age to a PNG file
+    #[cfg(feature = "chart")]
+    #[arg(
+        long = "chart",
+        value_name = "FILE",
+        conflicts_with_all = ["list", "stdin", "input", "check"]
+    )]
+    chart: Option<PathBuf>,
+
+    /// Size of the --chart image in pixels
+    #[cfg(feature = "chart")]
+    #[arg(
+        long = "chart-size",
+        value_name = "WIDTHxHEIGHT",
+        default_value_t = chart::ChartSize::default(),
+        requires = "chart"
+    )]
+    chart_size: chart::ChartSize,
+}
+
+impl Args {
+    fn format(&self) -> Format {
+        if let Some(format) = self.format {
+            format
+        } else if self.json {
+            Format::Json
+        } else if self.csv {
+            Format::Csv
+        } else {
+            self.output_file()
+                .and_then(Format::for_path)
+                .unwrap_or(Format::Text)
+        }
+    }
+
+    /// The `--output` file, unless it is missing or `-` for stdout.
+    fn output_file(&self) -> Option<&Path> {
+        self.output
+            .as_deref()
+            .filter(|path| *path != Path::new("-"))
+    }
+
+    /// JSON is indented for people reading a terminal and compact for everything else.
+    fn pretty_json(&self) -> bool {
+        !self.compact && self.to_terminal()
+    }
+
+    /// Whether results go to a terminal rather than a pipe or file.
+    fn to_terminal(&self) -> bool {
+        self.output_file().is_none() && Term::stdout().is_term()
+    }
+
+    /// Whether to write ANSI colors, given `--color`, `--no-color`, and NO_COLOR.
+    fn use_color(&self) -> bool {
+        let choice = if self.no_color || self.plain {
+            ColorChoice::Never
+        } else {
+            self.color
+        };
+        choice.enabled(self.to_terminal())
+    }
+
+    /// The human lifespan human ages are measured against: `--human-max`, else
+    /// `--country`'s life expectancy, else [`HUMAN_MAX`].
+    fn human_max(&self) -> Result<f32, AppError> {
+        if let Some(country) = &self.country {
+            return Country::find(country)
+                .map(|country| country.life_expectancy)
+                .ok_or_else(|| {
+                    AppError::InvalidInput(format!(
+                        "unknown country '{}'; use a two-letter code such as JP or US",
+                        country
+                    ))
+                });
+        }
+        match self.human_max {
+            Some(years) if !(years > 0.0 && years.is_finite()) => Err(AppError::InvalidInput(
+                "--human-max must be a positive number of years".to_string(),
+            )),
+            years => Ok(years.unwrap_or(HUMAN_MAX)),
+        }
+    }
+
+    /// How each pet of `--stdin` or `--input` is converted.
+    fn batch_settings(&self) -> Result<batch::Settings, AppError> {
+        let mut settings = batch::Settings::load()?;
+        settings.standard = self.standard.clone();
+        settings.human_max = self.human_max()?;
+        if let Some(target) = self.survive_to {
+            validate_age(target)?;
+        }
+        settings.survive_to = self.survive_to;
+        settings.warnings = self.warning_mode();
+        settings.precision = self.precision();
+        settings.bars = self.bars()?;
+        Ok(settings)
+    }
+
+    /// What to do with conversion warnings: `--strict` or `--no-warn`.
+    fn warning_mode(&self) -> WarningMode {
+        if self.strict {
+            WarningMode::Fail
+        } else if self.no_warn {
+            WarningMode::Ignore
+        } else {
+            WarningMode::Print
+        }
+    }
+
+    /// How precisely human ages are computed: `--no-round`, `--precision`, or
+    /// else one decimal.
+    fn precision(&self) -> Precision {
+        match self.precision {
+            _ if self.no_round => Precision::Exact,
+            Some(decimals) => Precision::Decimals(decimals),
+            None => Precision::default(),
+        }
+    }
+
+    /// Decimal places of human years: `--precision`, else one.
+    fn decimals(&self) -> usize {
+        self.precision.map_or(1, usize::from)
+    }
+
+    /// `--suggest-distance`, falling back to the config file.
+    fn suggest_distance(&self) -> Option<usize> {
+        self.suggest_distance
+            .or_else(|| Config::load().ok()?.suggest_distance)
+    }
+
+    /// Bar characters from the flags, falling back to the config file.
+    fn bars(&self) -> Result<Bars, AppError> {
+        let config = Config::load()?;
+        let mut theme = Theme::named(
+            self.theme
+                .as_deref()
+                .or(config.theme.as_deref())
+                .unwrap_or("default"),
+            &config.themes,
+        )?;
+        if let Some(precision) = self.precision {
+            theme.percent_decimals = precision;
+        }
+        Ok(Bars {
+            style: self.bar_style,
+            fill: self.bar_char.or(config.bar_char),
+            empty: self.empty_char.or(config.empty_char).unwrap_or(' '),
+            width: self.width.map(usize::from),
+            gradient: self.use_color() && bar::truecolor(),
+            theme,
+            thresholds: self.thresholds.or(config.thresholds).unwrap_or_default(),
+            inline: self.inline,
+            decimals: self.decimals(),
+        })
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Show reference facts about an animal type or breed
+    Info {
+        /// Animal type to describe
+        #[arg(value_name = "ANIMAL", required_unless_present = "breed")]
+        animal: Option<String>,
+
+        /// Breed to describe (e.g. beagle or maine_coon)
+        #[arg(long = "breed", value_name = "BREED", conflicts_with = "animal")]
+        breed: Option<String>,
+    },
+    /// Print the JSON Schema of the JSON output
+    Schema,
+    /// Fit a formula to known ages and save it as a custom standard
+    Calibrate(calibrate::CalibrateArgs),
+    /// List past conversions from the history log
+    #[cfg(feature = "history")]
+    History(history::HistoryArgs),
+    /// Run the strict input parsers on literal inputs or a corpus
+    #[cfg(feature = "parse")]
+    Parse(parse_cmd::ParseArgs),
 }
 
 #[derive(Error, Debug)]
-enum AppError {
+pub enum AppError {
     #[error("Missing required arguments: --type and --age")]
     MissingArgs,
     #[error("Unknown animal type: {0}")]
     UnknownAnimal(String),
+    #[error("Ambiguous animal type: {0} could be {}", or_list(.1))]
+    AmbiguousAnimal(String, Vec<String>),
+    #[error("Unknown breed: {0}")]
+    UnknownBreed(String),
     #[error("Invalid age: {0}")]
     InvalidAge(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("{location}: {source}")]
+    Batch {
+        location: String,
+        source: Box<AppError>,
+    },
+    #[error("Failed to read input: {0}")]
+    Io(#[from] io::Error),
+    #[error("Config: {0}")]
+    Config(String),
+    #[error("Invalid --check expression {0}")]
+    InvalidCheck(String),
+    #[error("Check failed")]
+    CheckFailed,
+    #[error("{0} (--strict)")]
+    Strict(String),
+    #[error("Failed to write PDF: {0}")]
+    Pdf(String),
+    #[cfg(feature = "chart")]
+    #[error("Failed to draw chart: {0}")]
+    Chart(String),
+    #[cfg(feature = "history")]
+    #[error("History: {0}")]
+    History(String),
+    #[cfg(feature = "parquet")]
+    #[error("Failed to write Parquet: {0}")]
+    Parquet(String),
+    #[cfg(feature = "parse")]
+    #[error("{0} parser run(s) panicked")]
+    ParserPanic(usize),
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Animal {
-    SmallDog,
-    MediumDog,
-    BigDog,
-    Cat,
-    Horse,
-    Pig,
-    Parakeet,
-    Snake,
-    Goldfish,
-    Rabbit,
-    Hamster,
-}
-
-impl Animal {
-    fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "small_dog" => Some(Animal::SmallDog),
-            "medium_dog" => Some(Animal::MediumDog),
-            "big_dog" => Some(Animal::BigDog),
-            "cat" => Some(Animal::Cat),
-            "horse" => Some(Animal::Horse),
-            "pig" => Some(Animal::Pig),
-            "parakeet" => Some(Animal::Parakeet),
-            "snake" => Some(Animal::Snake),
-            "goldfish" => Some(Animal::Goldfish),
-            "rabbit" => Some(Animal::Rabbit),
-            "hamster" => Some(Animal::Hamster),
-            _ => None,
+impl From<parse::ParseError> for AppError {
+    fn from(err: parse::ParseError) -> AppError {
+        use parse::ParseError;
+        match err {
+            ParseError::UnknownAnimal(name) => AppError::UnknownAnimal(name),
+            ParseError::AmbiguousAnimal(name, candidates) => {
+                AppError::AmbiguousAnimal(name, candidates)
+            }
+            ParseError::InvalidNumber | ParseError::UnknownUnit(_) | ParseError::AgeOutOfRange => {
+                AppError::InvalidAge(err.to_string())
+            }
+            err => AppError::InvalidInput(err.to_string()),
         }
     }
+}
 
-    fn key(&self) -> &'static str {
-        match self {
-            Animal::SmallDog => "small_dog",
-            Animal::MediumDog => "medium_dog",
-            Animal::BigDog => "big_dog",
-            Animal::Cat => "cat",
-            Animal::Horse => "horse",
-            Animal::Pig => "pig",
-            Animal::Parakeet => "parakeet",
-            Animal::Snake => "snake",
-            Animal::Goldfish => "goldfish",
-            Animal::Rabbit => "rabbit",
-            Animal::Hamster => "hamster",
-        }
-    }
-
-    fn description(&self) -> &'static str {
-        match self {
-            Animal::SmallDog => "Small dog (e.g., terrier)",
-            Animal::MediumDog => "Medium dog (e.g., spaniel)",
-            Animal::BigDog => "Large dog (e.g., retriever)",
-            Animal::Cat => "Domestic cat",
-            Animal::Horse => "Horse",
-            Animal::Pig => "Pig",
-            Animal::Parakeet => "Parakeet / budgie",
-            Animal::Snake => "Common pet snake",
-            Animal::Goldfish => "Goldfish",
-            Animal::Rabbit => "Rabbit",
-            Animal::Hamster => "Hamster",
-        }
-    }
-
-    fn max_lifespan(&self) -> f32 {
-        match self {
-            Animal::SmallDog => 16.0,
-            Animal::MediumDog => 14.0,
-            Animal::BigDog => 10.0,
-            Animal::Cat => 18.0,
-            Animal::Horse => 30.0,
-            Animal::Pig => 20.0,
-            Animal::Parakeet => 10.0,
-            Animal::Snake => 20.0,
-            Animal::Goldfish => 15.0,
-            Animal::Rabbit => 12.0,
-            Animal::Hamster => 3.0,
-        }
-    }
+fn main() {
+    let args = Args::parse();
+    // Like `test`, --check reserves exit code 1 for "condition is false".
+    let error_code = if args.check.is_some() { 2 } else { 1 };
 
-    fn human_years(&self, age: f32) -> f32 {
-        match self {
-            Animal::SmallDog => {
-                if age <= 2.0 {
-                    age * 12.5
-                } else {
-                    25.0 + (age - 2.0) * 4.5
-                }
-            }
-            Animal::MediumDog => {
-                if age <= 2.0 {
-                    age * 10.5
-                } else {
-                    21.0 + (age - 2.0) * 5.0
-                }
-            }
-            Animal::BigDog => {
-                if age <= 2.0 {
-                    age * 9.0
-                } else {
-                    18.0 + (age - 2.0) * 7.0
-                }
-            }
-            Animal::Cat => {
-                if age <= 2.0 {
-                    age * 12.5
-                } else {
-                    25.0 + (age - 2.0) * 4.0
-                }
-            }
-            Animal::Horse => 6.5 + age * 4.0,
-            Animal::Pig => age * 5.0,
-            Animal::Parakeet => age * 5.0,
-            Animal::Snake => age * 5.3,
-            Animal::Goldfish => age * 5.0,
-            Animal::Rabbit => {
-                if age <= 2.0 {
-                    age * 12.0
-                } else {
-                    24.0 + (age - 2.0) * 4.0
-                }
-            }
-            Animal::Hamster => age * 25.0,
+    if let Err(err) = main_inner(&args) {
+        if let AppError::CheckFailed = err {
+            exit(1);
         }
+        eprintln!("{}", error_report(err, args.suggest_distance()));
+        exit(error_code);
     }
 }
 
-fn main() {
-    if let Err(err) = main_inner() {
-        if let AppError::UnknownAnimal(ref animal) = err {
-            if let Some(suggestion) = suggest_animal(animal) {
-                eprintln!(
-                    "Unknown animal type: {}. Did you mean '{}'?\nUse --list to view valid options.",
-                    animal, suggestion
-                );
-            } else {
-                eprintln!(
+/// What to print for `err`: where in a batch it happened, if anywhere, and a
+/// suggestion for a mistyped animal or breed within `suggest_distance` edits.
+fn error_report(err: AppError, suggest_distance: Option<usize>) -> String {
+    let (location, err) = match err {
+        AppError::Batch { location, source } => (Some(location), *source),
+        err => (None, err),
+    };
+    let message = match err {
+        AppError::UnknownAnimal(animal) => {
+            match suggest_animal(&animal, suggest_distance).as_slice() {
+                [] => format!(
                     "Unknown animal type: {}\nUse --list to view valid options.",
                     animal
-                );
+                ),
+                suggestions => format!(
+                    "Unknown animal type: {}. Did you mean {}?\nUse --list to view valid options.",
+                    animal,
+                    quoted_or_list(suggestions)
+                ),
             }
-        } else {
-            eprintln!("Error: {}", err);
         }
-        exit(1);
+        err @ AppError::AmbiguousAnimal(..) => {
+            format!("{}.\nUse --list to view valid options.", err)
+        }
+        AppError::UnknownBreed(breed) => match suggest_breed(&breed, suggest_distance).as_slice() {
+            [] => format!("Unknown breed: {}", breed),
+            suggestions => format!(
+                "Unknown breed: {}. Did you mean {}?",
+                breed,
+                quoted_or_list(suggestions)
+            ),
+        },
+        err => format!("Error: {}", err),
+    };
+    match location {
+        Some(location) => format!("{}: {}", location, message),
+        None => message,
     }
 }
 
-fn main_inner() -> Result<(), AppError> {
-    let args = Args::parse();
+fn main_inner(args: &Args) -> Result<(), AppError> {
+    match &args.command {
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(
+                *shell,
+                &mut Args::command(),
+                "animal-age",
+                &mut io::stdout(),
+            );
+            return Ok(());
+        }
+        Some(Command::Info { animal, breed }) => {
+            return match (animal, breed) {
+                (_, Some(breed)) => show_breed_info(breed),
+                (Some(animal), None) => show_animal_info(animal),
+                (None, None) => Err(AppError::MissingArgs),
+            };
+        }
+        Some(Command::Schema) => {
+            let schema = serde_json::to_string_pretty(&schema::json_schema()).unwrap();
+            println!("{}", schema);
+            return Ok(());
+        }
+        Some(Command::Calibrate(calibrate_args)) => return calibrate::run(calibrate_args),
+        #[cfg(feature = "history")]
+        Some(Command::History(history_args)) => return history::run(history_args),
+        #[cfg(feature = "parse")]
+        Some(Command::Parse(parse_args)) => return parse_cmd::run(parse_args),
+        None => {}
+    }
+
+    if onboarding::should_run() {
+        return onboarding::run(args);
+    }
 
     if args.list {
-        list_animals();
+        let mut out = open_output(args)?;
+        catalog::print(
+            args.format(),
+            args.filter.as_deref(),
+            args.pretty_json(),
+            &mut out,
+        )?;
+        return out.finish();
+    }
+
+    if args.stdin {
+        let input = io::BufReader::new(io::stdin());
+        let mut out = open_output(args)?;
+        batch::run_batch(
+            input,
+            args.format(),
+            args.pretty_json(),
+            args.batch_settings()?,
+            &mut out,
+        )?;
+        return out.finish();
+    }
+
+    if let Some(path) = &args.input {
+        let mut out = open_output(args)?;
+        batch::run_input(
+            path,
+            args.format(),
+            args.pretty_json(),
+            args.batch_settings()?,
+            &mut out,
+        )?;
+        return out.finish();
+    }
+
+    let profile = match &args.profile {
+        Some(name) => Some(Config::load()?.profile(name)?.clone()),
+        None => None,
+    };
+    let weight_dog = args.weight.map(Animal::dog_for_weight);
+    let animals = args
+        .animal
+        .clone()
+        .map(expand_animals)
+        .or_else(|| weight_dog.map(|dog| vec![dog.key().to_string()]))
+        .or_else(|| profile.as_ref().map(|p| vec![p.animal.clone()]));
+    let dob = args.dob.or_else(|| {
+        profile
+            .as_ref()
+            .filter(|_| args.age.is_none())
+            .map(|p| p.dob)
+    });
+    let age = match (args.age, args.dob, &profile) {
+        // A placeholder until the formula is known; see `set_age_from_human`.
+        _ if args.from_human.is_some() => Some(0.0),
+        (Some(age), _, _) => Some(age),
+        (None, Some(dob), _) => Some(age_from_dob(dob)?),
+        (None, None, Some(profile)) => Some(profile.age()),
+        (None, None, None) => None,
+    };
+
+    let Some(age) = age else {
+        return Err(AppError::MissingArgs);
+    };
+
+    validate_age(age)?;
+    if let Some(target) = args.survive_to {
+        validate_age(target)?;
+    }
+
+    let mut conversions = match (&args.breed, args.weight, animals) {
+        (Some(breed), _, _) => vec![convert_breed(breed, age)?],
+        (None, Some(weight), _) => vec![convert_weight(weight, age)],
+        (None, None, Some(animals)) => animals
+            .iter()
+            .map(|animal| convert(animal, age))
+            .collect::<Result<_, _>>()?,
+        (None, None, None) => return Err(AppError::MissingArgs),
+    };
+    let config = Config::load()?;
+    let standards = Standards::from_config(&config);
+    let stages = Stages::from_config(&config);
+    let human_max = args.human_max()?;
+    for conversion in &mut conversions {
+        conversion.dob = dob;
+        conversion.human_max = human_max;
+        conversion.survive_to = args.survive_to;
+        if let Some(sex) = args.sex {
+            conversion.set_sex(sex);
+        }
+        if args.neutered {
+            conversion.set_neutered();
+        }
+        standards.apply(args.standard.as_deref(), conversion)?;
+        stages.apply(conversion)?;
+        if let Some(human) = args.from_human {
+            conversion.set_age_from_human(human)?;
+        }
+        conversion.set_precision(args.precision());
+        conversion.check_warnings(args.warning_mode())?;
+    }
+    if let Some(key) = args.sort {
+        key.sort(&mut conversions);
+    }
+
+    if let Some(check) = &args.check {
+        if !conversions.iter().all(|c| check.matches(c)) {
+            return Err(AppError::CheckFailed);
+        }
         return Ok(());
     }
 
-    let (animals, age) = match (args.animal.as_ref(), args.age) {
-        (Some(a), Some(y)) => (a, y),
-        _ => return Err(AppError::MissingArgs),
+    #[cfg(feature = "chart")]
+    if let Some(path) = &args.chart {
+        chart::write_png(&conversions, path, args.chart_size)?;
+    }
+
+    #[cfg(feature = "history")]
+    if history::enabled() {
+        if let Err(err) = history::record(&conversions, args.profile.as_deref()) {
+            eprintln!("Warning: could not record history: {}", err);
+        }
+    }
+
+    let mut out = open_output(args)?;
+    let notes = args.format() == Format::Text && !args.quiet;
+    if let (Some(weight), Some(dog), true) = (args.weight, weight_dog, notes) {
+        writeln!(
+            out,
+            "A {} dog counts as a {}, but its rates and estimated lifespan of {} years are interpolated by weight.\n",
+            weight,
+            dog.key(),
+            conversions[0].animal_max
+        )?;
+    }
+    if notes && conversions.iter().any(|c| c.neutered) {
+        for conversion in conversions.iter().filter(|c| c.neutered) {
+            let factor = conversion.animal.neutered_lifespan_factor().unwrap_or(1.0);
+            writeln!(
+                out,
+                "Lifespans of a neutered {} are adjusted {:.0}% longer.",
+                conversion.key(),
+                (factor - 1.0) * 100.0
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    run_calc(conversions, args, &mut out)?;
+    out.finish()
+}
+
+/// Where results go: the `--output` file, or stdout. Binary output such as PDF is
+/// refused on a terminal.
+fn open_output(args: &Args) -> Result<Destination, AppError> {
+    match args.output_file() {
+        Some(path) => Destination::file(path),
+        None if args.format().is_binary() && Term::stdout().is_term() => {
+            Err(AppError::InvalidInput(
+                "refusing to write binary output to the terminal; use --output FILE".to_string(),
+            ))
+        }
+        None => Ok(Destination::stdout()),
+    }
+}
+
+fn show_animal_info(animal_str: &str) -> Result<(), AppError> {
+    let animal = Animal::from_key(animal_str)
+        .ok_or_else(|| AppError::UnknownAnimal(animal_str.to_string()))?;
+    println!("{} - {}", animal.key(), animal.description());
+    if !animal.aliases().is_empty() {
+        println!("  Also accepted as: {}", animal.aliases().join(", "));
+        if let Some(note) = animal.alias_note() {
+            println!("  Note: {}", note);
+        }
+    }
+    println!("  Typical max lifespan: {} years", animal.max_lifespan());
+    let standards = Standards::load()?;
+    println!("  Standards: {}", standards.covering(animal).join(", "));
+    Ok(())
+}
+
+fn show_breed_info(breed_str: &str) -> Result<(), AppError> {
+    let breed =
+        Breed::find(breed_str).ok_or_else(|| AppError::UnknownBreed(breed_str.to_string()))?;
+    println!("{} ({})", breed.name, breed.key);
+    let label = if breed.animal.is_dog() {
+        "Size class:"
+    } else {
+        "Formula:"
     };
+    println!(
+        "  {:17} {} - {}",
+        label,
+        breed.animal.key(),
+        breed.animal.description()
+    );
+    println!(
+        "  Typical lifespan: {}-{} years",
+        breed.lifespan.0, breed.lifespan.1
+    );
+    Ok(())
+}
 
+fn validate_age(age: f32) -> Result<(), AppError> {
+    if !age.is_finite() {
+        return Err(AppError::InvalidAge(
+            "Age must be a finite number".to_string(),
+        ));
+    }
     if age < 0.0 {
         return Err(AppError::InvalidAge("Age cannot be negative".to_string()));
     }
-
-    run_calc(animals.to_vec(), age, &args)?;
+    if age > MAX_AGE_YEARS {
+        return Err(AppError::InvalidAge(format!(
+            "Age cannot exceed {} years",
+            MAX_AGE_YEARS
+        )));
+    }
     Ok(())
 }
 
-fn list_animals() {
-    println!("Available animals:\n");
-    let animal_variants = [
-        Animal::SmallDog,
-        Animal::MediumDog,
-        Animal::BigDog,
-        Animal::Cat,
-        Animal::Horse,
-        Animal::Pig,
-        Animal::Parakeet,
-        Animal::Snake,
-        Animal::Goldfish,
-        Animal::Rabbit,
-        Animal::Hamster,
-    ];
-    for animal in animal_variants {
-        println!("  {:12} - {}", animal.key(), animal.description());
-    }
-}
-
-fn run_calc(animals: Vec<String>, age: f32, args: &Args) -> Result<(), AppError> {
-    struct ResultRow {
-        display_label: String,
-        chart_label: String,
-        human_age: f32,
-        animal_max: f32,
-    }
-
-    let mut results = Vec::new();
-
-    for animal_str in animals {
-        let animal_lower = animal_str.to_lowercase();
-        let animal_type = Animal::from_str(&animal_lower)
-            .ok_or_else(|| AppError::UnknownAnimal(animal_str.clone()))?;
-
-        let animal_max = animal_type.max_lifespan();
-        if age > animal_max * 1.5 {
-            eprintln!(
-                "Warning: Age {} exceeds typical {} lifespan of {} years.",
-                age, animal_str, animal_max
-            );
+/// Parses an age for clap with [`parse::parse_age`], which takes a unit such as
+/// `18mo` and rejects NaN, infinity, and anything outside 0 to [`MAX_AGE_YEARS`],
+/// which would otherwise draw broken bars.
+fn parse_years(input: &str) -> Result<f32, String> {
+    parse::parse_age(input).map_err(|err| format!("'{}': {}", input, err))
+}
+
+/// Age in years on today's date, to one decimal, of an animal born on `dob`.
+fn age_from_dob(dob: Date) -> Result<f32, AppError> {
+    let today = Date::today();
+    if dob > today {
+        return Err(AppError::InvalidAge(format!(
+            "Date of birth {} is in the future",
+            dob
+        )));
+    }
+    Ok((dob.years_until(today) * 10.0).round() / 10.0)
+}
+
+/// A single animal/age conversion, shared by the chart, JSON, and batch paths.
+#[derive(Clone)]
+struct Conversion {
+    name: Option<String>,
+    animal: Animal,
+    label: String,
+    age: f32,
+    human_age: f32,
+    /// The range `human_age` likely falls in, given the formula's uncertainty.
+    human_age_low: f32,
+    human_age_high: f32,
+    /// The human lifespan `human_age` is measured against.
+    human_max: f32,
+    /// How long the animal usually lives; at most `animal_max`.
+    animal_typical: f32,
+    animal_max: f32,
+    breed: Option<&'static Breed>,
+    /// How `animal_max` was estimated, when it isn't a fixed table value.
+    lifespan_method: Option<&'static str>,
+    /// Date of birth, when the age was computed from one.
+    dob: Option<Date>,
+    /// The sex the lifespans were adjusted for, if given.
+    sex: Option<Sex>,
+    /// Whether the lifespans were lengthened for a neutered animal.
+    neutered: bool,
+    /// The future age `--survive-to` asks about.
+    survive_to: Option<f32>,
+    /// The formula behind `human_age`, the standard it belongs to, and where it
+    /// comes from.
+    formula: Formula,
+    standard: Cow<'static, str>,
+    formula_source: Cow<'static, str>,
+    /// When the animal enters each life stage.
+    life_stages: LifeStages,
+    /// How precisely `human_age` and its range are reported.
+    precision: Precision,
+    warnings: Vec<String>,
+    /// Something worth knowing that is not a problem, such as what an alias was
+    /// taken as; printed to stderr and left out of the results.
+    note: Option<String>,
+}
+
+impl Conversion {
+    fn new(animal: Animal, label: &str, age: f32, animal_max: f32) -> Conversion {
+        let mut conversion = Conversion {
+            name: None,
+            animal,
+            label: label.to_string(),
+            age,
+            human_age: 0.0,
+            human_age_low: 0.0,
+            human_age_high: 0.0,
+            human_max: HUMAN_MAX,
+            animal_typical: animal.typical_lifespan().min(animal_max),
+            animal_max,
+            breed: None,
+            lifespan_method: None,
+            dob: None,
+            sex: None,
+            neutered: false,
+            survive_to: None,
+            formula: animal.formula(),
+            standard: Standard::Classic.key().into(),
+            formula_source: FORMULA_SOURCE.into(),
+            life_stages: animal.life_stages(),
+            precision: Precision::default(),
+            warnings: Vec::new(),
+            note: None,
+        };
+        conversion.warnings.extend(conversion.age_warning());
+        conversion.update_human_age();
+        conversion
+    }
+
+    /// The warning for an age far past the maximum lifespan, if it is.
+    fn age_warning(&self) -> Option<String> {
+        (self.age > self.animal_max * 1.5).then(|| {
+            format!(
+                "Age {} exceeds typical {} lifespan of {} years.",
+                self.age, self.label, self.animal_max
+            )
+        })
+    }
+
+    /// Makes `change`, which may move the age or the lifespans, then swaps the
+    /// age warning for the one that fits afterwards.
+    fn recheck_age(&mut self, change: impl FnOnce(&mut Conversion)) {
+        let before = self.age_warning();
+        change(self);
+        self.warnings
+            .retain(|warning| Some(warning) != before.as_ref());
+        if let Some(warning) = self.age_warning() {
+            self.warnings.insert(0, warning);
         }
+    }
+
+    fn human_years(&self, age: f32) -> f32 {
+        self.formula.human_years(age)
+    }
+
+    /// Converts with `formula` from `standard` instead.
+    fn set_formula(
+        &mut self,
+        formula: Formula,
+        standard: Cow<'static, str>,
+        source: Cow<'static, str>,
+    ) {
+        self.formula = formula;
+        self.standard = standard;
+        self.formula_source = source;
+        self.update_human_age();
+    }
 
-        let human_age = (animal_type.human_years(age) * 10.0).round() / 10.0;
+    /// Sets the age to the one the formula converts to `human` years, to one
+    /// decimal unless the precision is exact.
+    fn set_age_from_human(&mut self, human: f32) -> Result<(), AppError> {
+        let age = self.formula.animal_years(human).ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "no {} age converts to {} human years",
+                self.key(),
+                human
+            ))
+        })?;
+        let age = match self.precision {
+            Precision::Exact => age,
+            Precision::Decimals(_) => Precision::default().round(age),
+        };
+        self.recheck_age(|conversion| conversion.age = age);
+        self.update_human_age();
+        Ok(())
+    }
+
+    /// Fails on the first warning under [`WarningMode::Fail`], or drops the
+    /// warnings under [`WarningMode::Ignore`].
+    fn check_warnings(&mut self, mode: WarningMode) -> Result<(), AppError> {
+        match mode {
+            WarningMode::Print => {}
+            WarningMode::Fail => {
+                if let Some(warning) = self.warnings.first() {
+                    return Err(AppError::Strict(warning.clone()));
+                }
+            }
+            WarningMode::Ignore => self.warnings.clear(),
+        }
+        Ok(())
+    }
+
+    /// Multiplies the typical and maximum lifespans by `factor`.
+    fn scale_lifespans(&mut self, factor: f32) {
+        self.recheck_age(|conversion| {
+            conversion.animal_max = (conversion.animal_max * factor * 10.0).round() / 10.0;
+            conversion.animal_typical = (conversion.animal_typical * factor * 10.0).round() / 10.0;
+        });
+    }
+
+    /// Adjusts the lifespans for an animal of `sex`.
+    fn set_sex(&mut self, sex: Sex) {
+        self.scale_lifespans(self.animal.sex_lifespan_factor(sex));
+        self.sex = Some(sex);
+    }
+
+    /// Lengthens the lifespans for a neutered animal, or warns that there is no
+    /// documented gain for its species.
+    fn set_neutered(&mut self) {
+        match self.animal.neutered_lifespan_factor() {
+            Some(factor) => {
+                self.scale_lifespans(factor);
+                self.neutered = true;
+            }
+            None => self.warnings.push(format!(
+                "No documented lifespan gain for neutered {}; lifespans left as they are.",
+                self.key()
+            )),
+        }
+    }
+
+    /// Reports `human_age` and its range to `precision` from now on.
+    fn set_precision(&mut self, precision: Precision) {
+        self.precision = precision;
+        self.update_human_age();
+    }
+
+    /// Sets `human_age` and its range from the formula, to the conversion's
+    /// precision.
+    fn update_human_age(&mut self) {
+        let round = |years: f32| self.precision.round(years);
+        let human_years = self.human_years(self.age);
+        let margin = self.formula.uncertainty();
+        self.human_age = round(human_years);
+        self.human_age_low = round(human_years * (1.0 - margin));
+        self.human_age_high = round(human_years * (1.0 + margin));
+    }
+
+    /// The conversion `share` of the way to its age, for `--animate` frames.
+    fn scaled(&self, share: f32) -> Conversion {
+        Conversion {
+            age: self.age * share,
+            human_age: self.human_age * share,
+            human_age_low: self.human_age_low * share,
+            human_age_high: self.human_age_high * share,
+            ..self.clone()
+        }
+    }
+
+    /// The share of its typical lifespan the animal has lived, past 1 once it
+    /// outlives it. Every bar, percentage, and `animal_progress` uses this.
+    fn animal_progress(&self) -> f32 {
+        self.age / self.animal_typical
+    }
+
+    /// The percentile of ages at death the animal's age falls at: the share of
+    /// its kind, in percent, that die younger.
+    fn age_percentile(&self) -> f32 {
+        (1.0 - survival(self.age, self.animal_typical, self.animal_max)) * 100.0
+    }
+
+    /// `human_age` in months, when it is young enough that years would read as
+    /// a bare fraction.
+    fn human_age_months(&self) -> Option<f32> {
+        let human_years = self.human_years(self.age);
+        (human_years < HUMAN_MONTHS_BELOW).then(|| self.precision.round(human_years * 12.0))
+    }
 
-        if args.json {
-            print_json(&animal_str, age, human_age, animal_max);
+    /// `human_age` for a sentence, e.g. "29.0 human years" or "30 human months",
+    /// followed by its likely range when `range` is set.
+    fn human_age_phrase(&self, decimals: usize, range: bool) -> String {
+        let human_years = self.human_years(self.age);
+        let (phrase, low, high) = if human_years < HUMAN_MONTHS_BELOW {
+            let months = human_years * 12.0;
+            let margin = self.formula.uncertainty();
+            let whole = |months: f32| format!("{:.0}", months);
+            (
+                format!("{} human months", whole(months)),
+                whole(months * (1.0 - margin)),
+                whole(months * (1.0 + margin)),
+            )
         } else {
-            results.push(ResultRow {
-                display_label: animal_str,
-                chart_label: animal_type.key().to_string(),
-                human_age,
-                animal_max,
-            });
+            let years = |years: f32| format!("{:.*}", decimals, years);
+            (
+                format!("{} human years", years(self.human_age)),
+                years(self.human_age_low),
+                years(self.human_age_high),
+            )
+        };
+        if range {
+            format!("{} ({}–{})", phrase, low, high)
+        } else {
+            phrase
         }
     }
 
-    if args.json {
-        return Ok(());
+    fn life_stage(&self) -> LifeStage {
+        self.life_stages.stage(self.age)
+    }
+
+    /// Human years per calendar year at the animal's age.
+    fn aging_rate(&self) -> f32 {
+        self.formula.aging_rate(self.age)
+    }
+
+    /// The chance, from 0 to 1, that an animal alive at its age lives to `target`.
+    fn survival_chance(&self, target: f32) -> f32 {
+        let alive = |age: f32| survival(age, self.animal_typical, self.animal_max);
+        if target <= self.age {
+            return 1.0;
+        }
+        alive(target) / alive(self.age)
+    }
+
+    /// The key shown next to the lifespan bar: the breed if known, else the animal type.
+    fn key(&self) -> &'static str {
+        self.breed.map_or(self.animal.key(), |breed| breed.key)
+    }
+}
+
+fn convert(animal_str: &str, age: f32) -> Result<Conversion, AppError> {
+    let animal = parse::parse_animal(animal_str)?;
+    let by_alias = Animal::from_key(animal_str).is_some() && !animal.is_key(animal_str);
+    // "Small Dog", "cats", and "ham" are labeled with the key; aliases as given.
+    let label = if by_alias { animal_str } else { animal.key() };
+    let mut conversion = Conversion::new(animal, label, age, animal.max_lifespan());
+    if by_alias {
+        conversion.note = animal
+            .alias_note()
+            .map(|note| format!("'{}' is taken as {}: {}.", animal_str, animal.key(), note));
     }
+    Ok(conversion)
+}
+
+/// A dog of `weight`, with its rates and lifespan interpolated between the size
+/// classes rather than taken from the nearest one.
+fn convert_weight(weight: Weight, age: f32) -> Conversion {
+    let dog = Animal::dog_for_weight(weight);
+    let lifespan = Animal::mixed_dog_lifespan(weight);
+    let mut conversion = Conversion {
+        lifespan_method: Some(MIXED_DOG_METHOD),
+        animal_typical: Animal::mixed_dog_typical_lifespan(weight).min(lifespan),
+        ..Conversion::new(dog, dog.key(), age, lifespan)
+    };
+    conversion.set_formula(
+        Animal::dog_formula_for_weight(weight),
+        CONTINUOUS_DOG_STANDARD.into(),
+        CONTINUOUS_DOG_SOURCE.into(),
+    );
+    conversion
+}
+
+fn convert_breed(breed_str: &str, age: f32) -> Result<Conversion, AppError> {
+    let breed =
+        Breed::find(breed_str).ok_or_else(|| AppError::UnknownBreed(breed_str.to_string()))?;
+    Ok(Conversion {
+        breed: Some(breed),
+        animal_typical: breed.typical_lifespan(),
+        ..Conversion::new(breed.animal, breed.key, age, breed.max_lifespan())
+    })
+}
+
+/// What to do with a conversion's warnings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WarningMode {
+    /// Print them to stderr.
+    #[default]
+    Print,
+    /// Fail the conversion instead, for validating data files.
+    Fail,
+    /// Drop them.
+    Ignore,
+}
+
+fn print_warnings(conversion: &Conversion) {
+    for warning in &conversion.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+}
 
+/// Prints the conversion's note to stderr, whatever the format.
+fn print_note(conversion: &Conversion) {
+    if let Some(note) = &conversion.note {
+        eprintln!("Note: {}", note);
+    }
+}
+
+fn run_calc(results: Vec<Conversion>, args: &Args, out: &mut dyn Write) -> Result<(), AppError> {
     for result in &results {
-        println!(
-            "{} years old {} ≈ {:.1} human years",
-            age, result.display_label, result.human_age
-        );
+        print_note(result);
+    }
+    if !args.format().embeds_warnings() {
+        for result in &results {
+            print_warnings(result);
+        }
     }
 
-    if results.is_empty() {
+    if args.quiet {
+        for result in &results {
+            writeln!(out, "{:.*}", args.decimals(), result.human_age)?;
+        }
         return Ok(());
     }
 
-    let mut max_label_len = 0;
-    if results.len() == 1 {
-        max_label_len = max_label_len.max("Human".len());
-        max_label_len = max_label_len.max(results[0].chart_label.len());
-    } else {
-        for result in &results {
-            max_label_len = max_label_len.max(format!("human({})", result.chart_label).len());
-            max_label_len = max_label_len.max(result.chart_label.len());
+    match args.format() {
+        Format::Json => {
+            output::print_json_report(&results, args.pretty_json(), out)?;
+            return Ok(());
+        }
+        Format::Ndjson => {
+            for result in &results {
+                output::print_json(result, false, out)?;
+            }
+            return Ok(());
+        }
+        Format::Csv => {
+            output::print_csv(&results, true, out)?;
+            return Ok(());
+        }
+        Format::Tsv => {
+            output::print_tsv(&results, true, out)?;
+            return Ok(());
         }
+        Format::CsvLong
+        | Format::Yaml
+        | Format::Toml
+        | Format::Xml
+        | Format::Html
+        | Format::Latex
+        | Format::Typst
+        | Format::Pdf
+        | Format::Svg
+        | Format::Ics => {
+            return output::print_report(&results, args.format(), &args.bars()?, out);
+        }
+        #[cfg(feature = "parquet")]
+        Format::Parquet => {
+            return output::print_report(&results, args.format(), &args.bars()?, out)
+        }
+        Format::Text => {}
     }
-    let label_width = max_label_len.max(10);
 
-    println!("\nLife Progress:\n");
-    for (idx, result) in results.iter().enumerate() {
-        if results.len() == 1 {
-            show_lifespan_bars(
-                "Human",
-                result.human_age.min(HUMAN_MAX),
-                HUMAN_MAX,
-                args.no_color,
-                label_width,
-            );
-        } else {
-            let human_label = format!("human({})", result.chart_label);
-            show_lifespan_bars(
-                &human_label,
-                result.human_age.min(HUMAN_MAX),
-                HUMAN_MAX,
-                args.no_color,
-                label_width,
-            );
+    let bars = args.bars()?;
+    let color = args.use_color();
+    let animated = args.animate && args.to_terminal();
+    if args.plain {
+        output::print_plain(&results, &bars, out)?;
+        return Ok(());
+    }
+    if results.len() > 1 {
+        match args.from_human {
+            Some(human) => writeln!(out, "{} human years in animal years:\n", human)?,
+            None => writeln!(out, "{} years old in human years:\n", results[0].age)?,
         }
+        animate(out, animated, |share, out| {
+            let frame: Vec<Conversion> = results.iter().map(|c| c.scaled(share)).collect();
+            if !args.vertical {
+                return output::print_comparison(
+                    &frame,
+                    args.borders,
+                    color,
+                    args.emoji,
+                    &bars,
+                    out,
+                );
+            }
+            let columns: Vec<Column> = frame
+                .iter()
+                .map(|conversion| Column {
+                    label: if args.emoji {
+                        format!("{} {}", conversion.animal.emoji(), conversion.key())
+                    } else {
+                        conversion.key().to_string()
+                    },
+                    progress: conversion.animal_progress(),
+                    paint: Some(theme::species_paint(conversion.animal)),
+                })
+                .collect();
+            vertical::write_columns(&columns, &bars, color, out)
+        })?;
+        output::print_legend(&results, color, out)?;
+        if args.curve {
+            writeln!(out)?;
+            curve::write_curves(&results, &bars, color, out)?;
+        }
+        return Ok(());
+    }
 
-        show_lifespan_bars(
-            &result.chart_label,
-            age.min(result.animal_max),
-            result.animal_max,
-            args.no_color,
-            label_width,
-        );
+    let Some(result) = results.first() else {
+        return Ok(());
+    };
+    writeln!(
+        out,
+        "{} years old {} ≈ {}",
+        result.age,
+        result.label,
+        result.human_age_phrase(args.decimals(), true)
+    )?;
+    let percentile = result.age_percentile();
+    if percentile < 1.0 {
+        writeln!(out, "Younger than 99% of {} lifespans.", result.key())?;
+    } else {
+        writeln!(
+            out,
+            "Older than ~{:.0}% of {} lifespans.",
+            percentile,
+            result.key()
+        )?;
+    }
+    let stage = result.life_stage();
+    match stage.next() {
+        Some(next) => writeln!(
+            out,
+            "Life stage: {}, until {} at {} years.",
+            stage.key(),
+            next.key(),
+            result.life_stages.start(next)
+        )?,
+        None => writeln!(out, "Life stage: {}.", stage.key())?,
+    }
+    if let Some(months) = checkup_months(result.animal, stage).filter(|_| !args.no_checkup) {
+        let every = match months {
+            1 => "month".to_string(),
+            12 => "year".to_string(),
+            months => format!("{} months", months),
+        };
+        writeln!(out, "Recommended wellness exams: every {}.", every)?;
+    }
+    writeln!(
+        out,
+        "At age {}, a {} ages ~{:.*} human years per calendar year.",
+        result.age,
+        result.key(),
+        args.decimals(),
+        result.aging_rate()
+    )?;
+    if let Some(target) = result.survive_to {
+        writeln!(
+            out,
+            "Chance of reaching {} years: ~{:.0}%.",
+            target,
+            result.survival_chance(target) * 100.0
+        )?;
+    }
 
-        if idx + 1 < results.len() {
-            println!();
+    let (human_label, animal_label) = if args.emoji {
+        (
+            format!("{} Human", HUMAN_EMOJI),
+            format!("{} {}", result.animal.emoji(), result.key()),
+        )
+    } else {
+        ("Human".to_string(), result.key().to_string())
+    };
+    let layout = BarLayout::new(animal_label.width().max(10), &bars, args.to_terminal());
+    if args.art {
+        write!(out, "\n{}", art::portrait(result.animal))?;
+    }
+    writeln!(out, "\nLife Progress:\n")?;
+    animate(out, animated, |share, out| {
+        let frame = result.scaled(share);
+        if args.vertical {
+            let columns = [
+                Column {
+                    label: human_label.clone(),
+                    progress: frame.human_age / frame.human_max,
+                    paint: None,
+                },
+                Column {
+                    label: animal_label.clone(),
+                    progress: frame.animal_progress(),
+                    paint: None,
+                },
+            ];
+            vertical::write_columns(&columns, &bars, color, out)
+        } else if bars.inline {
+            show_inline_bar(out, &animal_label, &frame, color, &bars, &layout)
+        } else {
+            show_both_bars(
+                out,
+                &human_label,
+                &animal_label,
+                &frame,
+                color,
+                &bars,
+                &layout,
+            )
         }
+    })?;
+    writeln!(out)?;
+    if args.curve {
+        curve::write_curves(&results, &bars, color, out)?;
     }
-    println!();
 
     Ok(())
 }
 
-fn suggest_animal(input: &str) -> Option<String> {
-    let animals = [
-        "small_dog",
-        "medium_dog",
-        "big_dog",
-        "cat",
-        "horse",
-        "pig",
-        "parakeet",
-        "snake",
-        "goldfish",
-        "rabbit",
-        "hamster",
-    ];
+/// `items` joined as "a", "a or b", or "a, b, or c".
+fn or_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.clone(),
+        [first, second] => format!("{} or {}", first, second),
+        [rest @ .., last] => format!("{}, or {}", rest.join(", "), last),
+    }
+}
+
+/// `suggestions` in single quotes, joined by [`or_list`].
+fn quoted_or_list(suggestions: &[String]) -> String {
+    let quoted: Vec<String> = suggestions.iter().map(|s| format!("'{}'", s)).collect();
+    or_list(&quoted)
+}
+
+/// Replaces `all` among the `-t` values with the key of every supported animal,
+/// and a category key such as `rodents` with the keys of its members, in
+/// display order, as [`parse::parse_group`] expands them.
+fn expand_animals(animals: Vec<String>) -> Vec<String> {
     animals
+        .into_iter()
+        .flat_map(|animal| match parse::parse_group(&animal) {
+            Some(members) => members.iter().map(|a| a.key().to_string()).collect(),
+            None => vec![animal],
+        })
+        .collect()
+}
+
+/// How many suggestions to offer for a mistyped animal or breed.
+const SUGGESTIONS: usize = 3;
+
+/// The animal keys, aliases, category keys, or `all` closest to a mistyped
+/// `-t` value, naming each animal at most once. Keys with the value as one of
+/// their words come first, so `dog` suggests the dog size classes.
+fn suggest_animal(input: &str, max_distance: Option<usize>) -> Vec<String> {
+    let word = input.trim().to_lowercase();
+    let containing = Animal::ALL
+        .iter()
+        .map(|animal| animal.key())
+        .filter(|key| key.split('_').any(|part| part == word))
+        .map(str::to_string);
+    let keys = Animal::ALL.iter().map(|animal| animal.key());
+    let aliases = Animal::ALL
         .iter()
-        .min_by_key(|&&animal| levenshtein(input, animal))
-        .filter(|&&animal| levenshtein(input, animal) < 3)
-        .map(|&animal| animal.to_string())
+        .flat_map(|animal| animal.aliases())
+        .copied();
+    let groups = Category::ALL.iter().map(|category| category.key());
+    let mut named = Vec::new();
+    let near = closest(
+        input,
+        keys.chain(aliases)
+            .chain(groups)
+            .chain([parse::ALL_ANIMALS]),
+        max_distance,
+    );
+    containing
+        .chain(near)
+        .filter(|name| match Animal::from_key(name) {
+            Some(animal) if named.contains(&animal) => false,
+            Some(animal) => {
+                named.push(animal);
+                true
+            }
+            None => true,
+        })
+        .take(SUGGESTIONS)
+        .collect()
+}
+
+fn suggest_breed(input: &str, max_distance: Option<usize>) -> Vec<String> {
+    let mut suggestions = closest(
+        input,
+        Breed::all().iter().map(|breed| breed.key),
+        max_distance,
+    );
+    suggestions.truncate(SUGGESTIONS);
+    suggestions
+}
+
+/// The `keys` within `max_distance` of `input`, or [`default_suggest_distance`]
+/// without one, nearest first and otherwise in the order given.
+fn closest<'a>(
+    input: &str,
+    keys: impl Iterator<Item = &'a str>,
+    max_distance: Option<usize>,
+) -> Vec<String> {
+    let max_distance = max_distance.unwrap_or_else(|| default_suggest_distance(input));
+    let mut near: Vec<(usize, &str)> = keys
+        .map(|key| (levenshtein(input, key), key))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    near.sort_by_key(|&(distance, _)| distance);
+    near.into_iter().map(|(_, key)| key.to_string()).collect()
 }
 
+/// Edits allowed between a mistyped name and a suggestion: two, or one per
+/// four characters of longer names.
+fn default_suggest_distance(input: &str) -> usize {
+    (input.chars().count() / 4).max(2)
+}
+
+/// The human lifespan human ages are measured against unless `--human-max` or
+/// `--country` gives another.
 const HUMAN_MAX: f32 = 80.0;
 
-fn show_lifespan_bars(label: &str, age: f32, max: f32, no_color: bool, label_width: usize) {
+/// Human ages below this many years are told in months in the text output.
+const HUMAN_MONTHS_BELOW: f32 = 2.0;
+
+/// Frames drawn by `--animate`, one every [`FRAME_TIME`].
+const ANIMATION_FRAMES: u32 = 20;
+const FRAME_TIME: Duration = Duration::from_millis(50);
+
+/// Writes the bars drawn by `frame`, which is passed how far along they are.
+/// When `animated`, they are drawn filling up over about a second, each frame
+/// replacing the last; otherwise they are drawn once, full.
+fn animate(
+    out: &mut dyn Write,
+    animated: bool,
+    mut frame: impl FnMut(f32, &mut dyn Write) -> io::Result<()>,
+) -> io::Result<()> {
+    if !animated {
+        return frame(1.0, out);
+    }
     let term = Term::stdout();
-    let term_width = term.size().1 as usize;
-    let gutter = label_width + 8;
-    let available_width = term_width.saturating_sub(gutter);
-    let total_width = available_width.min(50);
-    let pct = age / max;
-    let filled = (pct * total_width as f32) as usize;
-    let empty = total_width - filled;
-
-    let color_code = if no_color {
-        ""
-    } else if pct >= 0.8 {
-        color::RED
-    } else if pct >= 0.6 {
-        color::YELLOW
-    } else {
-        color::CYAN
-    };
+    let mut lines = 0;
+    for i in 1..=ANIMATION_FRAMES {
+        let mut buffer = Vec::new();
+        frame(i as f32 / ANIMATION_FRAMES as f32, &mut buffer)?;
+        if lines > 0 {
+            std::thread::sleep(FRAME_TIME);
+            term.clear_last_lines(lines)?;
+        }
+        out.write_all(&buffer)?;
+        out.flush()?;
+        lines = buffer.iter().filter(|&&b| b == b'\n').count();
+    }
+    Ok(())
+}
 
-    let bar = format!(
-        "{}{} {}{}",
-        color_code,
-        "=".repeat(filled),
-        " ".repeat(empty),
-        if no_color { "" } else { color::RESET }
-    );
+/// Shown next to the human bar with `--emoji`.
+const HUMAN_EMOJI: &str = "🧑";
 
-    println!(
-        "{:label_width$} |{}| {:>3.0}%",
-        label,
-        bar,
-        pct * 100.0,
-        label_width = label_width
-    );
+/// Width of the lifespan bars when the terminal is wide enough.
+const MAX_BAR_WIDTH: usize = 50;
+
+/// Column widths shared by a block of lifespan bars, worked out once for all of
+/// them so the terminal size is only queried once. Output that is not going to a
+/// terminal gets full-width bars rather than whatever size the controlling
+/// terminal happens to have.
+struct BarLayout {
+    label_width: usize,
+    bar_width: usize,
+}
+
+impl BarLayout {
+    fn new(label_width: usize, bars: &Bars, to_terminal: bool) -> BarLayout {
+        let bar_width = bars.width.unwrap_or_else(|| {
+            if !to_terminal {
+                return MAX_BAR_WIDTH;
+            }
+            let term_width = Term::stdout().size().1 as usize;
+            let gutter = label_width + 8;
+            term_width.saturating_sub(gutter).min(MAX_BAR_WIDTH)
+        });
+        BarLayout {
+            label_width,
+            bar_width,
+        }
+    }
+}
+
+/// The human and animal lifespan bars, one under the other.
+fn show_both_bars(
+    out: &mut dyn Write,
+    human_label: &str,
+    animal_label: &str,
+    conversion: &Conversion,
+    color: bool,
+    bars: &Bars,
+    layout: &BarLayout,
+) -> io::Result<()> {
+    show_lifespan_bars(
+        out,
+        human_label,
+        Span {
+            age: conversion.human_age,
+            typical: conversion.human_max,
+            max: conversion.human_max,
+            band: Some((conversion.human_age_low, conversion.human_age_high)),
+        },
+        color,
+        bars,
+        layout,
+    )?;
+    show_lifespan_bars(
+        out,
+        animal_label,
+        Span {
+            age: conversion.age,
+            typical: conversion.animal_typical,
+            max: conversion.animal_max,
+            band: None,
+        },
+        color,
+        bars,
+        layout,
+    )
+}
+
+/// The `--inline` bar: the animal's share of its typical lifespan and its human
+/// equivalent's share of a human one, as two markers on one line.
+fn show_inline_bar(
+    out: &mut dyn Write,
+    label: &str,
+    conversion: &Conversion,
+    color: bool,
+    bars: &Bars,
+    layout: &BarLayout,
+) -> io::Result<()> {
+    let animal = conversion.animal_progress();
+    let human = conversion.human_age / conversion.human_max;
+    let padding = layout.label_width.saturating_sub(label.width());
+    let label = format!("{}{}", label, " ".repeat(padding));
+    writeln!(
+        out,
+        "{} |{} | {} {} {} {} {}",
+        bars.theme.label(&label, color),
+        bars.render_inline(animal, human, layout.bar_width, color),
+        bar::paint_mark(bar::ANIMAL_MARK, color),
+        bars.theme.percent(animal),
+        bar::paint_mark(bar::HUMAN_MARK, color),
+        bars.theme.percent(human),
+        bar::years_left(conversion.animal_typical - conversion.age)
+    )
 }
 
-#[derive(Serialize)]
-struct Output {
-    animal: String,
+/// An age and the lifespan it is measured against, for one lifespan bar.
+struct Span {
     age: f32,
-    human_age: f32,
-    animal_max_lifespan: f32,
-    human_max_lifespan: f32,
-    animal_progress: f32,
-    human_progress: f32,
-}
-
-fn print_json(animal: &str, age: f32, human_age: f32, animal_max: f32) {
-    let output = Output {
-        animal: animal.to_string(),
-        age,
-        human_age,
-        animal_max_lifespan: animal_max,
-        human_max_lifespan: HUMAN_MAX,
-        animal_progress: age / animal_max,
-        human_progress: human_age / HUMAN_MAX,
+    /// How long the animal usually lives, which the percentage is of.
+    typical: f32,
+    max: f32,
+    /// The range the age likely falls in, when it is an estimate.
+    band: Option<(f32, f32)>,
+}
+
+/// Writes one lifespan bar. When the typical lifespan falls short of the
+/// maximum, the bar fills up to the typical lifespan and a bracket carries it on
+/// to the maximum. An estimated age shades the range it likely falls in more
+/// lightly. The years left of the typical lifespan follow the percentage.
+fn show_lifespan_bars(
+    out: &mut dyn Write,
+    label: &str,
+    span: Span,
+    color: bool,
+    bars: &Bars,
+    layout: &BarLayout,
+) -> io::Result<()> {
+    let pct = span.age / span.typical;
+
+    let theme = &bars.theme;
+    let escape = theme
+        .paint(bars.thresholds.level(pct))
+        .escape()
+        .filter(|_| color);
+    let stops = theme.gradient().filter(|_| bars.gradient);
+    let body = if span.typical < span.max {
+        bars.render_range(
+            span.age / span.max,
+            span.typical / span.max,
+            layout.bar_width + 1,
+            stops,
+        )
+    } else if let Some((low, high)) = span.band {
+        let (low, high) = (low / span.max, high / span.max);
+        format!("{} ", bars.render_band(low, high, layout.bar_width, stops))
+    } else {
+        match stops {
+            Some(stops) => format!("{} ", bars.render_gradient(pct, layout.bar_width, stops)),
+            None => format!("{} ", bars.render(pct, layout.bar_width)),
+        }
     };
-    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    let bar = match escape.filter(|_| stops.is_none()) {
+        Some(escape) => format!("{}{}{}", escape, body, color::RESET),
+        None => body,
+    };
+    let padding = layout.label_width.saturating_sub(label.width());
+    let label = format!("{}{}", label, " ".repeat(padding));
+
+    writeln!(
+        out,
+        "{} |{}| {} {}",
+        theme.label(&label, color),
+        bar,
+        theme.percent(pct),
+        bar::years_left(span.typical - span.age)
+    )
 }
 
 #[cfg(test)]
@@ -443,22 +1781,148 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_cat_age_conversion() {
-        let cat = Animal::Cat;
-        assert_eq!(cat.human_years(1.0), 12.5);
-        assert_eq!(cat.human_years(3.0), 29.0);
+    fn test_error_report_snapshots() {
+        let errors = [
+            AppError::UnknownAnimal("catt".to_string()),
+            AppError::UnknownAnimal("zebra".to_string()),
+            AppError::UnknownAnimal("gat".to_string()),
+            AppError::AmbiguousAnimal(
+                "go".to_string(),
+                vec!["goldfish".to_string(), "goat".to_string()],
+            ),
+            AppError::UnknownBreed("beagel".to_string()),
+            AppError::Batch {
+                location: "Line 3".to_string(),
+                source: Box::new(AppError::InvalidAge("-1 is negative".to_string())),
+            },
+            AppError::MissingArgs,
+        ];
+        let report: Vec<String> = errors
+            .into_iter()
+            .map(|err| error_report(err, None))
+            .collect();
+        insta::assert_snapshot!(report.join("\n---\n"));
+    }
+
+    #[test]
+    fn test_parse_years() {
+        assert_eq!(parse_years(" 3 "), Ok(3.0));
+        assert_eq!(parse_years("18mo"), Ok(1.5));
+        for input in ["inf", "NaN", "-1", "201", "3 fortnights"] {
+            assert!(parse_years(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_expand_all_animals() {
+        let animals = expand_animals(vec!["cat".to_string(), " ALL".to_string()]);
+        assert_eq!(animals.len(), 1 + Animal::ALL.len());
+        assert_eq!(animals[..3], ["cat", "small_dog", "medium_dog"]);
+        assert_eq!(animals.last().unwrap(), Animal::ALL.last().unwrap().key());
+
+        let animals = expand_animals(vec!["equines".to_string(), "cat".to_string()]);
+        assert_eq!(animals, ["horse", "pony", "donkey", "mule", "cat"]);
+        assert_eq!(suggest_animal("rodnets", None), ["rodents"]);
+    }
+
+    #[test]
+    fn test_suggest_distance_grows_with_input() {
+        assert_eq!(default_suggest_distance("cat"), 2);
+        assert_eq!(default_suggest_distance("baerded_dragn"), 3);
+        // Three edits away: too many for a short name, not for a long one.
+        assert_eq!(suggest_animal("baerded_dragn", None), ["bearded_dragon"]);
+        assert!(suggest_animal("baerded_dragn", Some(2)).is_empty());
+        assert_eq!(
+            suggest_breed("yorkshir_teriers", None),
+            ["yorkshire_terrier"]
+        );
+    }
+
+    #[test]
+    fn test_suggestions() {
+        // One edit from cat, two from rat and cavy: nearest first.
+        assert_eq!(suggest_animal("catt", None), ["cat", "rat", "cavy"]);
+        assert_eq!(suggest_animal("catt", Some(1)), ["cat"]);
+        assert_eq!(suggest_animal("gat", None), ["cat", "goat", "rat"]);
+        assert_eq!(
+            suggest_animal("Dog", None),
+            ["small_dog", "medium_dog", "big_dog"]
+        );
+        // kitty and kitten are both cats.
+        assert_eq!(suggest_animal("kitte", None), ["kitty"]);
+        assert!(suggest_animal("zebra", None).is_empty());
+        assert!(suggest_animal("gat", Some(0)).is_empty());
+        assert_eq!(
+            quoted_or_list(&suggest_animal("cot", None)),
+            "'cat', 'cow', or 'goat'"
+        );
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let hamster = convert("ham", 1.0).unwrap();
+        assert_eq!(
+            (hamster.animal, hamster.label.as_str()),
+            (Animal::Hamster, "hamster")
+        );
+        match convert("go", 1.0) {
+            Err(AppError::AmbiguousAnimal(prefix, candidates)) => {
+                assert_eq!(prefix, "go");
+                assert_eq!(candidates, ["goldfish", "goat"]);
+            }
+            _ => panic!("'go' should be ambiguous"),
+        }
+        assert!(matches!(
+            convert("zebra", 1.0),
+            Err(AppError::UnknownAnimal(_))
+        ));
+        let three = ["a", "b", "c"].map(String::from);
+        assert_eq!(or_list(&three[..2]), "a or b");
+        assert_eq!(or_list(&three), "a, b, or c");
+    }
+
+    #[test]
+    fn test_age_warning_follows_the_age() {
+        let mut hamster = convert("hamster", 0.0).unwrap();
+        assert!(hamster.warnings.is_empty());
+        hamster.set_age_from_human(150.0).unwrap();
+        assert_eq!(hamster.warnings.len(), 1);
+        assert!(hamster.check_warnings(WarningMode::Fail).is_err());
+
+        // Lifespans stretched past the age take the warning away again.
+        let mut cat = convert("cat", 28.0).unwrap();
+        assert_eq!(cat.warnings.len(), 1);
+        cat.scale_lifespans(1.2);
+        assert!(cat.warnings.is_empty());
     }
 
     #[test]
-    fn test_max_lifespan() {
-        assert_eq!(Animal::SmallDog.max_lifespan(), 16.0);
-        assert_eq!(Animal::Hamster.max_lifespan(), 3.0);
+    fn test_alias_note() {
+        let doggo = convert("Doggo", 3.0).unwrap();
+        assert_eq!(doggo.animal, Animal::MediumDog);
+        let note = doggo.note.as_deref().unwrap();
+        assert!(note.starts_with("'Doggo' is taken as medium_dog: dogs age by size"));
+        // A note, not a warning, so --strict accepts it and JSON leaves it out.
+        assert!(doggo.warnings.is_empty());
+        assert!(convert("medium_dog", 3.0).unwrap().note.is_none());
+        assert!(convert("kitty", 3.0).unwrap().note.is_none());
     }
 
     #[test]
-    fn test_animal_from_str() {
-        assert!(Animal::from_str("cat").is_some());
-        assert!(Animal::from_str("CAT").is_some());
-        assert!(Animal::from_str("invalid").is_none());
+    fn test_bars_past_human_lifespan() {
+        // A 60-year-old macaw is about 89 in human years, past the 80-year
+        // human lifespan: a full bar, with the percentage carrying on past 100.
+        let macaw = convert("macaw", 60.0).unwrap();
+        let bars = Bars {
+            width: Some(10),
+            ..Bars::default()
+        };
+        let layout = BarLayout::new(6, &bars, false);
+        let mut out = Vec::new();
+        show_both_bars(&mut out, "Human", "macaw", &macaw, false, &bars, &layout).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "Human  |========== | 112% past typical lifespan");
+        assert!(lines[1].ends_with("120% past typical lifespan"));
     }
 }
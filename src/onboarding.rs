@@ -0,0 +1,170 @@
+//! Guided first-run experience for people who start `animal-age` with no arguments.
+
+use crate::config::{self, Config, Profile};
+use crate::{convert, quoted_or_list, run_calc, suggest_animal, validate_age, AppError, Args};
+use animal_age::animal::{Animal, MAX_AGE_YEARS};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use console::Term;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+/// True on the very first interactive run: no arguments, a TTY on both ends, and
+/// no config file written yet.
+pub fn should_run() -> bool {
+    std::env::args_os().len() == 1
+        && Term::stdout().is_term()
+        && io::stdin().is_terminal()
+        && config::path().is_some_and(|path| !path.exists())
+}
+
+pub fn run(args: &Args) -> Result<(), AppError> {
+    let term = Term::stdout();
+    term.write_line("Welcome to animal-age! Let's work out how old your pet is in human years.\n")?;
+
+    let animal = prompt_animal(&term, args.suggest_distance())?;
+    let age = prompt_age(&term, animal)?;
+    term.write_line("")?;
+    run_calc(vec![convert(animal.key(), age)?], args, &mut io::stdout())?;
+
+    // Saving the config (even an empty one) marks onboarding as done.
+    let mut config = Config::load()?;
+    let name = prompt(
+        &term,
+        "Save this pet as a profile? Enter a name, or press Enter to skip:",
+    )?;
+    if !name.is_empty() {
+        config
+            .profiles
+            .insert(name.clone(), Profile::from_age(animal.key(), age));
+    }
+    let path = config.save()?;
+    if !name.is_empty() {
+        term.write_line(&format!(
+            "Saved profile '{}' to {}. Next time, run `animal-age --profile {}`.",
+            name,
+            path.display(),
+            name
+        ))?;
+    }
+
+    if let Some(shell) = Shell::from_env() {
+        let answer = prompt(
+            &term,
+            &format!("Install shell completions for {}? [y/N]", shell),
+        )?;
+        if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+            match install_completions(shell)? {
+                Some(path) if shell == Shell::Zsh => term.write_line(&format!(
+                    "Installed completions to {}. ~/.zfunc isn't on zsh's fpath by default, so \
+                     add this line to your ~/.zshrc and restart your shell:\n  {}",
+                    path.display(),
+                    ZSH_FPATH_LINE
+                ))?,
+                Some(path) => term.write_line(&format!(
+                    "Installed completions to {} (restart your shell to use them).",
+                    path.display()
+                ))?,
+                None => term.write_line(&format!(
+                    "Automatic install isn't supported for {}; use `animal-age completions {}`.",
+                    shell, shell
+                ))?,
+            }
+        }
+    }
+
+    term.write_line("\nRun `animal-age --help` to see everything else it can do.")?;
+    Ok(())
+}
+
+fn prompt(term: &Term, question: &str) -> io::Result<String> {
+    term.write_line(question)?;
+    term.write_str("> ")?;
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(answer.trim().to_string())
+}
+
+fn prompt_animal(term: &Term, suggest_distance: Option<usize>) -> Result<Animal, AppError> {
+    loop {
+        let answer = prompt(
+            term,
+            "Which animal? Type its name or number (press Enter to see the list):",
+        )?;
+        if answer.is_empty() {
+            for (idx, animal) in Animal::ALL.iter().enumerate() {
+                term.write_line(&format!(
+                    "  {:2}. {:12} {}",
+                    idx + 1,
+                    animal.key(),
+                    animal.description()
+                ))?;
+            }
+            continue;
+        }
+
+        let by_number = answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|idx| Animal::ALL.get(idx).copied());
+        if let Some(animal) = by_number.or_else(|| Animal::from_key(&answer)) {
+            return Ok(animal);
+        }
+
+        match suggest_animal(&answer, suggest_distance).as_slice() {
+            [] => term.write_line(&format!("Unknown animal '{}'.", answer))?,
+            suggestions => term.write_line(&format!(
+                "Unknown animal '{}'. Did you mean {}?",
+                answer,
+                quoted_or_list(suggestions)
+            ))?,
+        }
+    }
+}
+
+fn prompt_age(term: &Term, animal: Animal) -> Result<f32, AppError> {
+    loop {
+        let answer = prompt(
+            term,
+            &format!(
+                "How old is your {}, in years (0 to {})?",
+                animal.key(),
+                MAX_AGE_YEARS
+            ),
+        )?;
+        match answer.parse::<f32>() {
+            Ok(age) if validate_age(age).is_ok() => return Ok(age),
+            _ => term.write_line(&format!(
+                "Please enter a number from 0 to {}, like 3 or 1.5.",
+                MAX_AGE_YEARS
+            ))?,
+        }
+    }
+}
+
+/// What `~/.zshrc` needs for zsh to find completions installed to `~/.zfunc`.
+const ZSH_FPATH_LINE: &str = "fpath+=~/.zfunc; autoload -U compinit && compinit";
+
+/// Writes the completion script to the shell's per-user completion directory.
+fn install_completions(shell: Shell) -> io::Result<Option<PathBuf>> {
+    let path = match shell {
+        Shell::Bash => dirs::data_dir().map(|d| d.join("bash-completion/completions/animal-age")),
+        Shell::Zsh => dirs::home_dir().map(|d| d.join(".zfunc/_animal-age")),
+        Shell::Fish => dirs::config_dir().map(|d| d.join("fish/completions/animal-age.fish")),
+        _ => None,
+    };
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::File::create(&path)?;
+    clap_complete::generate(shell, &mut Args::command(), "animal-age", &mut file);
+    Ok(Some(path))
+}
@@ -0,0 +1,1399 @@
+//! Machine-readable and tabular renderings of conversion results.
+
+use crate::bar::{paint_mark, years_left, Bars, Level, Thresholds, ANIMAL_MARK, HUMAN_MARK};
+use crate::theme::{species_paint, Paint};
+use crate::{ics, pdf, svg, AppError, Conversion};
+use animal_age::animal::Formula;
+use comfy_table::presets::{NOTHING, UTF8_FULL_CONDENSED};
+use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+use serde::{Serialize, Serializer};
+use std::io::{self, Write};
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// How results are written to stdout, selected with `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    /// JSON Lines: one compact object per line, for streaming consumers.
+    Ndjson,
+    Csv,
+    /// Tidy CSV with one `name,animal,metric,value` row per number.
+    CsvLong,
+    /// Tab-separated values with the same columns as CSV.
+    Tsv,
+    /// A YAML sequence with one mapping per result, using the JSON field names.
+    Yaml,
+    /// A `[[results]]` array of tables, using the JSON field names.
+    Toml,
+    /// A `<results>` document with one `<result>` element per result.
+    Xml,
+    /// A self-contained HTML page with a table and styled progress bars.
+    Html,
+    /// A LaTeX `tabular` (booktabs rules) for papers and slides.
+    Latex,
+    /// A Typst `#table` for papers and slides.
+    Typst,
+    /// A printable PDF with one page per pet; see `--output`.
+    Pdf,
+    /// An SVG image of the lifespan bars.
+    Svg,
+    /// An iCalendar file of upcoming birthdays and life-stage transitions; needs `--dob`.
+    Ics,
+    /// An Apache Parquet file with the JSON field names as columns.
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl Format {
+    /// The format implied by an `--output` file name, for formats that are only
+    /// useful as files.
+    pub fn for_path(path: &Path) -> Option<Format> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "pdf" => Some(Format::Pdf),
+            "svg" => Some(Format::Svg),
+            "ics" => Some(Format::Ics),
+            #[cfg(feature = "parquet")]
+            "parquet" => Some(Format::Parquet),
+            _ => None,
+        }
+    }
+
+    /// Documents and binary files are never written a line at a time, so batch
+    /// input is handed to the report writer instead.
+    pub fn is_document(self) -> bool {
+        matches!(self, Format::Pdf | Format::Svg | Format::Ics) || self.is_binary()
+    }
+
+    /// Formats whose results carry their warnings, which are then left off stderr.
+    pub fn embeds_warnings(self) -> bool {
+        matches!(
+            self,
+            Format::Json | Format::Ndjson | Format::Yaml | Format::Toml
+        )
+    }
+
+    /// Binary formats that would garble a terminal.
+    pub fn is_binary(self) -> bool {
+        match self {
+            Format::Pdf => true,
+            #[cfg(feature = "parquet")]
+            Format::Parquet => true,
+            _ => false,
+        }
+    }
+}
+
+/// What `--sort` orders results by, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    #[value(name = "human_age")]
+    HumanAge,
+    /// Share of the animal's typical lifespan already lived.
+    Progress,
+    /// The animal's typical maximum lifespan.
+    Lifespan,
+}
+
+impl SortKey {
+    fn value(self, conversion: &Conversion) -> f32 {
+        match self {
+            SortKey::HumanAge => conversion.human_age,
+            SortKey::Progress => conversion.animal_progress(),
+            SortKey::Lifespan => conversion.animal_max,
+        }
+    }
+
+    /// Sorts `conversions` from highest to lowest; ties keep their input order.
+    pub fn sort(self, conversions: &mut [Conversion]) {
+        conversions.sort_by(|a, b| self.value(b).total_cmp(&self.value(a)));
+    }
+}
+
+const CSV_HEADER: [&str; 9] = [
+    "name",
+    "animal",
+    "age",
+    "human_age",
+    "animal_max_lifespan",
+    "human_max_lifespan",
+    "animal_progress",
+    "human_progress",
+    "life_stage",
+];
+
+#[derive(Serialize)]
+pub struct Output {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub animal: String,
+    /// The dog size class whose formula was used, when `animal` is a breed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_class: Option<String>,
+    /// The sex the lifespans were adjusted for, from `--sex`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex: Option<String>,
+    /// Whether the lifespans were lengthened for a neutered animal, from `--neutered`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub neutered: bool,
+    pub age: f32,
+    pub human_age: f32,
+    /// The range `human_age` likely falls in, given the formula's uncertainty.
+    pub human_age_low: f32,
+    pub human_age_high: f32,
+    /// `human_age` in months, when it is under two years.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub human_age_months: Option<f32>,
+    /// How long the animal usually lives.
+    pub animal_typical_lifespan: f32,
+    pub animal_max_lifespan: f32,
+    /// How `animal_max_lifespan` was estimated, e.g. interpolated for `--mixed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifespan_method: Option<String>,
+    pub human_max_lifespan: f32,
+    pub animal_progress: f32,
+    pub human_progress: f32,
+    /// Percentage of animals of this kind that die younger than `age`.
+    pub age_percentile: f32,
+    /// Human years per calendar year at `age`.
+    pub aging_rate: f32,
+    /// The stage of life the animal is in at `age`.
+    pub life_stage: &'static str,
+    /// The chance of living to the age asked about with `--survive-to`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub survive_to: Option<SurviveTo>,
+    pub formula: FormulaOutput,
+    /// Warnings about the conversion, such as an age past the maximum lifespan.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A future age and the chance, from 0 to 1, of living to it.
+#[derive(Serialize)]
+pub struct SurviveTo {
+    pub age: f32,
+    pub probability: f32,
+}
+
+/// The conversion model behind `human_age`.
+#[derive(Serialize)]
+pub struct FormulaOutput {
+    pub name: &'static str,
+    pub standard: String,
+    pub parameters: Parameters,
+    pub source: String,
+}
+
+/// Formula parameters, serialized as a map in the formula's own order.
+pub struct Parameters(Vec<(&'static str, f32)>);
+
+impl Serialize for Parameters {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().copied())
+    }
+}
+
+impl FormulaOutput {
+    fn new(formula: Formula, standard: &str, source: &str) -> Self {
+        FormulaOutput {
+            name: formula.name(),
+            standard: standard.to_string(),
+            parameters: Parameters(formula.parameters()),
+            source: source.to_string(),
+        }
+    }
+}
+
+impl From<&Conversion> for Output {
+    fn from(conversion: &Conversion) -> Self {
+        Output {
+            name: conversion.name.clone(),
+            animal: conversion.label.clone(),
+            size_class: conversion
+                .breed
+                .filter(|breed| breed.animal.is_dog())
+                .map(|breed| breed.animal.key().to_string()),
+            sex: conversion.sex.map(|sex| sex.key().to_string()),
+            neutered: conversion.neutered,
+            age: conversion.age,
+            human_age: conversion.human_age,
+            human_age_low: conversion.human_age_low,
+            human_age_high: conversion.human_age_high,
+            human_age_months: conversion.human_age_months(),
+            animal_typical_lifespan: conversion.animal_typical,
+            animal_max_lifespan: conversion.animal_max,
+            lifespan_method: conversion.lifespan_method.map(str::to_string),
+            human_max_lifespan: conversion.human_max,
+            animal_progress: conversion.animal_progress(),
+            human_progress: conversion.human_age / conversion.human_max,
+            age_percentile: conversion.age_percentile().round(),
+            aging_rate: (conversion.aging_rate() * 100.0).round() / 100.0,
+            life_stage: conversion.life_stage().key(),
+            survive_to: conversion.survive_to.map(|age| SurviveTo {
+                age,
+                probability: (conversion.survival_chance(age) * 1000.0).round() / 1000.0,
+            }),
+            formula: FormulaOutput::new(
+                conversion.formula,
+                &conversion.standard,
+                &conversion.formula_source,
+            ),
+            warnings: conversion.warnings.clone(),
+        }
+    }
+}
+
+/// Version of the JSON output schema printed by `animal-age schema`. Bumped whenever
+/// a field is removed, renamed, or changes meaning.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// A JSON result tagged with the schema version it follows.
+#[derive(Serialize)]
+struct Versioned<T> {
+    schema_version: u32,
+    #[serde(flatten)]
+    document: T,
+}
+
+fn versioned(conversion: &Conversion) -> Versioned<Output> {
+    Versioned {
+        schema_version: SCHEMA_VERSION,
+        document: Output::from(conversion),
+    }
+}
+
+pub fn print_json(conversion: &Conversion, pretty: bool, out: &mut dyn Write) -> io::Result<()> {
+    let output = versioned(conversion);
+    let json = if pretty {
+        serde_json::to_string_pretty(&output)
+    } else {
+        serde_json::to_string(&output)
+    };
+    writeln!(out, "{}", json.unwrap())
+}
+
+/// The JSON document `--format json` writes, however many animals it holds and
+/// whether they come from flags or batch input. `age` comes after `results` so
+/// that a streamed batch can write it once every result is known.
+#[derive(Serialize)]
+struct JsonReport {
+    schema_version: u32,
+    version: &'static str,
+    results: Vec<Output>,
+    age: Option<f32>,
+}
+
+/// The top-level `age` of a JSON report: the age every result shares, or none
+/// when they differ, as they do between animals under `--from-human`, or when
+/// there are no results.
+#[derive(Debug, Default)]
+pub struct SharedAge {
+    age: Option<f32>,
+    mixed: bool,
+}
+
+impl SharedAge {
+    pub fn add(&mut self, age: f32) {
+        match self.age {
+            None if !self.mixed => self.age = Some(age),
+            Some(shared) if shared != age => {
+                self.age = None;
+                self.mixed = true;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self) -> Option<f32> {
+        self.age
+    }
+}
+
+/// Writes `conversions` as one JSON report: the schema version, the tool
+/// version, a `results` array, and the `age` they share.
+pub fn print_json_report(
+    conversions: &[Conversion],
+    pretty: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let report = JsonReport {
+        schema_version: SCHEMA_VERSION,
+        version: env!("CARGO_PKG_VERSION"),
+        results: conversions.iter().map(Output::from).collect(),
+        age: shared_age(conversions),
+    };
+    let json = if pretty {
+        serde_json::to_string_pretty(&report)
+    } else {
+        serde_json::to_string(&report)
+    };
+    writeln!(out, "{}", json.unwrap())
+}
+
+/// The start of a JSON report, up to the `[` that opens `results`, for batch
+/// output that streams the results in; [`render_array_item`] renders them and
+/// [`json_report_end`] closes the report.
+pub fn json_report_start(pretty: bool) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    if pretty {
+        format!(
+            "{{\n  \"schema_version\": {},\n  \"version\": \"{}\",\n  \"results\": [",
+            SCHEMA_VERSION, version
+        )
+    } else {
+        format!(
+            r#"{{"schema_version":{},"version":"{}","results":["#,
+            SCHEMA_VERSION, version
+        )
+    }
+}
+
+/// What follows the last result of a JSON report, given whether it has any and
+/// the age they share.
+pub fn json_report_end(pretty: bool, empty: bool, age: Option<f32>) -> String {
+    let age = serde_json::to_string(&age).unwrap();
+    match (pretty, empty) {
+        (true, false) => format!("\n  ],\n  \"age\": {}\n}}", age),
+        (true, true) => format!("],\n  \"age\": {}\n}}", age),
+        (false, _) => format!(r#"],"age":{}}}"#, age),
+    }
+}
+
+fn shared_age(conversions: &[Conversion]) -> Option<f32> {
+    let mut shared = SharedAge::default();
+    for conversion in conversions {
+        shared.add(conversion.age);
+    }
+    shared.get()
+}
+
+fn csv_record(conversion: &Conversion) -> [String; 9] {
+    let output = Output::from(conversion);
+    [
+        output.name.unwrap_or_default(),
+        output.animal,
+        output.age.to_string(),
+        output.human_age.to_string(),
+        output.animal_max_lifespan.to_string(),
+        output.human_max_lifespan.to_string(),
+        output.animal_progress.to_string(),
+        output.human_progress.to_string(),
+        output.life_stage.to_string(),
+    ]
+}
+
+const CSV_LONG_HEADER: [&str; 4] = ["name", "animal", "metric", "value"];
+
+/// Renders one conversion as tidy CSV: a row for each numeric CSV column, named in
+/// `metric`; the trailing `life_stage` is not a metric.
+fn csv_long_rows(conversion: &Conversion) -> String {
+    let [name, animal, values @ .., _life_stage] = csv_record(conversion);
+    CSV_HEADER[2..CSV_HEADER.len() - 1]
+        .iter()
+        .zip(values)
+        .map(|(metric, value)| csv_line([name.as_str(), animal.as_str(), metric, &value]))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn csv_line<I, T>(record: I) -> String
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(record)
+        .expect("writing CSV to memory cannot fail");
+    let bytes = writer.into_inner().expect("flushing to memory cannot fail");
+    String::from_utf8_lossy(&bytes).trim_end().to_string()
+}
+
+/// Joins fields with tabs. TSV has no quoting, so tabs and line breaks inside a
+/// field are replaced with spaces.
+pub fn tsv_line<I, T>(record: I) -> String
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    record
+        .into_iter()
+        .map(|field| field.as_ref().replace(['\t', '\n', '\r'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// What streaming output writes before the first result: the header row of the
+/// tabular formats, or the XML declaration and opening root element.
+pub fn header(format: Format) -> Option<String> {
+    match format {
+        Format::Csv => Some(csv_line(CSV_HEADER)),
+        Format::CsvLong => Some(csv_line(CSV_LONG_HEADER)),
+        Format::Tsv => Some(tsv_line(CSV_HEADER)),
+        Format::Xml => Some("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<results>".to_string()),
+        Format::Html => Some(HTML_HEADER.trim_end().to_string()),
+        Format::Latex => Some(LATEX_HEADER.trim_end().to_string()),
+        Format::Typst => Some(TYPST_HEADER.trim_end().to_string()),
+        Format::Text
+        | Format::Json
+        | Format::Ndjson
+        | Format::Yaml
+        | Format::Toml
+        | Format::Pdf
+        | Format::Svg
+        | Format::Ics => None,
+        #[cfg(feature = "parquet")]
+        Format::Parquet => None,
+    }
+}
+
+/// What streaming output writes after the last result.
+pub fn footer(format: Format) -> Option<String> {
+    match format {
+        Format::Xml => Some("</results>".to_string()),
+        Format::Html => Some("</tbody>\n</table>\n</body>\n</html>".to_string()),
+        Format::Latex => Some("\\bottomrule\n\\end{tabular}".to_string()),
+        Format::Typst => Some(")".to_string()),
+        _ => None,
+    }
+}
+
+/// Writes CSV rows for `conversions`, preceded by the header when `header` is set.
+pub fn print_csv(conversions: &[Conversion], header: bool, out: &mut dyn Write) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    if header {
+        writer.write_record(CSV_HEADER)?;
+    }
+    for conversion in conversions {
+        writer.write_record(csv_record(conversion))?;
+    }
+    writer.flush()
+}
+
+/// Writes TSV rows for `conversions`, preceded by the header when `header` is set.
+pub fn print_tsv(conversions: &[Conversion], header: bool, out: &mut dyn Write) -> io::Result<()> {
+    if header {
+        writeln!(out, "{}", tsv_line(CSV_HEADER))?;
+    }
+    for conversion in conversions {
+        writeln!(out, "{}", tsv_line(csv_record(conversion)))?;
+    }
+    out.flush()
+}
+
+/// Renders one conversion as a single line of streaming batch output, with
+/// `bars` setting where HTML bars change color and the decimals of each number.
+pub fn render_line(conversion: &Conversion, format: Format, bars: &Bars) -> String {
+    match format {
+        Format::Json => serde_json::to_string(&Output::from(conversion)).unwrap(),
+        Format::Ndjson => serde_json::to_string(&versioned(conversion)).unwrap(),
+        Format::Csv => csv_line(csv_record(conversion)),
+        Format::CsvLong => csv_long_rows(conversion),
+        Format::Tsv => tsv_line(csv_record(conversion)),
+        Format::Yaml => yaml_item(conversion),
+        Format::Toml => toml_item(conversion),
+        Format::Xml => xml_item(conversion),
+        Format::Html => html_row(conversion, bars),
+        Format::Latex => latex_row(conversion, bars.decimals),
+        Format::Typst => typst_row(conversion, bars.decimals),
+        Format::Pdf | Format::Svg | Format::Ics => {
+            unreachable!("documents are written whole, never line by line")
+        }
+        #[cfg(feature = "parquet")]
+        Format::Parquet => unreachable!("Parquet is written in row groups, never line by line"),
+        Format::Text => format!(
+            "{} years old {} ≈ {}",
+            conversion.age,
+            conversion.label,
+            conversion.human_age_phrase(bars.decimals, false)
+        ),
+    }
+}
+
+/// Renders one conversion as a YAML sequence entry, so consecutive entries form a
+/// single sequence.
+fn yaml_item(conversion: &Conversion) -> String {
+    let yaml = serde_yaml::to_string(&[Output::from(conversion)]).unwrap();
+    yaml.trim_end().to_string()
+}
+
+/// Renders one conversion as a `[[results]]` table, so consecutive tables form a
+/// single array.
+fn toml_item(conversion: &Conversion) -> String {
+    #[derive(Serialize)]
+    struct Results {
+        results: [Output; 1],
+    }
+    let results = Results {
+        results: [Output::from(conversion)],
+    };
+    toml::to_string(&results).unwrap().trim_end().to_string()
+}
+
+/// Escapes text for XML and HTML element content.
+pub fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Renders one conversion as a `<result>` element. Child elements follow the JSON
+/// field names and order; optional fields are left out when absent.
+fn xml_item(conversion: &Conversion) -> String {
+    let output = Output::from(conversion);
+    let mut fields = Vec::new();
+    if let Some(name) = output.name {
+        fields.push(("name", name));
+    }
+    fields.push(("animal", output.animal));
+    if let Some(size_class) = output.size_class {
+        fields.push(("size_class", size_class));
+    }
+    if let Some(sex) = output.sex {
+        fields.push(("sex", sex));
+    }
+    if output.neutered {
+        fields.push(("neutered", output.neutered.to_string()));
+    }
+    fields.push(("age", output.age.to_string()));
+    fields.push(("human_age", output.human_age.to_string()));
+    fields.push(("human_age_low", output.human_age_low.to_string()));
+    fields.push(("human_age_high", output.human_age_high.to_string()));
+    if let Some(months) = output.human_age_months {
+        fields.push(("human_age_months", months.to_string()));
+    }
+    fields.push((
+        "animal_typical_lifespan",
+        output.animal_typical_lifespan.to_string(),
+    ));
+    fields.push((
+        "animal_max_lifespan",
+        output.animal_max_lifespan.to_string(),
+    ));
+    if let Some(method) = output.lifespan_method {
+        fields.push(("lifespan_method", method));
+    }
+    fields.push(("human_max_lifespan", output.human_max_lifespan.to_string()));
+    fields.push(("animal_progress", output.animal_progress.to_string()));
+    fields.push(("human_progress", output.human_progress.to_string()));
+    fields.push(("age_percentile", output.age_percentile.to_string()));
+    fields.push(("aging_rate", output.aging_rate.to_string()));
+    fields.push(("life_stage", output.life_stage.to_string()));
+    if let Some(survive_to) = output.survive_to {
+        fields.push(("survive_to_age", survive_to.age.to_string()));
+        fields.push(("survive_to_probability", survive_to.probability.to_string()));
+    }
+
+    let mut xml = String::from("  <result>\n");
+    for (tag, value) in fields {
+        xml.push_str(&format!("    <{0}>{1}</{0}>\n", tag, escape_markup(&value)));
+    }
+    let formula = output.formula;
+    xml.push_str("    <formula>\n");
+    xml.push_str(&format!("      <name>{}</name>\n", formula.name));
+    xml.push_str(&format!(
+        "      <standard>{}</standard>\n",
+        formula.standard
+    ));
+    xml.push_str("      <parameters>\n");
+    for (tag, value) in formula.parameters.0 {
+        xml.push_str(&format!("        <{0}>{1}</{0}>\n", tag, value));
+    }
+    xml.push_str("      </parameters>\n");
+    xml.push_str(&format!(
+        "      <source>{}</source>\n",
+        escape_markup(&formula.source)
+    ));
+    xml.push_str("    </formula>\n");
+    xml.push_str("  </result>");
+    xml
+}
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>animal-age report</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+  table { border-collapse: collapse; }
+  th, td { padding: 0.4rem 0.8rem; border-bottom: 1px solid #ddd; text-align: left; }
+  td.num { text-align: right; }
+  .bar { width: 12rem; height: 0.9rem; background: #eee; border-radius: 0.45rem; overflow: hidden; }
+  .bar > div { height: 100%; }
+  .low { background: #2aa198; }
+  .mid { background: #d4a017; }
+  .high { background: #c0392b; }
+</style>
+</head>
+<body>
+<h1>Animal age report</h1>
+<table>
+<thead>
+<tr><th>Name</th><th>Animal</th><th>Age</th><th>Human age</th><th>Lifespan</th><th>Human lifespan</th></tr>
+</thead>
+<tbody>
+"#;
+
+/// A progress bar cell, colored at the same `thresholds` as the terminal bars.
+fn html_bar(progress: f32, thresholds: &Thresholds) -> String {
+    let pct = (progress * 100.0).clamp(0.0, 100.0);
+    let class = match thresholds.level(progress) {
+        Level::High => "high",
+        Level::Mid => "mid",
+        Level::Low => "low",
+    };
+    format!(
+        r#"<td><div class="bar" title="{0:.0}%"><div class="{1}" style="width: {0:.1}%"></div></div></td>"#,
+        pct, class
+    )
+}
+
+/// Renders one conversion as a row of the HTML report table, with ages to
+/// `bars.decimals` places.
+fn html_row(conversion: &Conversion, bars: &Bars) -> String {
+    let output = Output::from(conversion);
+    format!(
+        r#"<tr><td>{}</td><td>{}</td><td class="num">{:.*}</td><td class="num">{:.*}</td>{}{}</tr>"#,
+        escape_markup(output.name.as_deref().unwrap_or("-")),
+        escape_markup(&output.animal),
+        bars.decimals,
+        output.age,
+        bars.decimals,
+        output.human_age,
+        html_bar(output.animal_progress, &bars.thresholds),
+        html_bar(output.human_progress, &bars.thresholds)
+    )
+}
+
+/// Needs `\usepackage{booktabs}` in the preamble.
+const LATEX_HEADER: &str = r"\begin{tabular}{llrrrr}
+\toprule
+Name & Animal & Age & Human age & Lifespan (\%) & Human lifespan (\%) \\
+\midrule
+";
+
+const TYPST_HEADER: &str = "#table(
+  columns: 6,
+  align: (left, left, right, right, right, right),
+  table.header[*Name*][*Animal*][*Age*][*Human age*][*Lifespan*][*Human lifespan*],
+";
+
+/// Escapes LaTeX's special characters for use in text.
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes the characters that are markup inside a Typst content block.
+fn escape_typst(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '[' | ']' | '#' | '*' | '_' | '`' | '$' | '<' | '>' | '@' | '~' | '/'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Renders one conversion as a LaTeX table row, every number to `decimals` places.
+fn latex_row(conversion: &Conversion, decimals: usize) -> String {
+    let output = Output::from(conversion);
+    format!(
+        r"{} & {} & {:.*} & {:.*} & {:.*} & {:.*} \\",
+        escape_latex(output.name.as_deref().unwrap_or("--")),
+        escape_latex(&output.animal),
+        decimals,
+        output.age,
+        decimals,
+        output.human_age,
+        decimals,
+        output.animal_progress * 100.0,
+        decimals,
+        output.human_progress * 100.0
+    )
+}
+
+/// Renders one conversion as a Typst table row, every number to `decimals` places.
+fn typst_row(conversion: &Conversion, decimals: usize) -> String {
+    let output = Output::from(conversion);
+    format!(
+        "  [{}], [{}], [{:.*}], [{:.*}], [{:.*}%], [{:.*}%],",
+        escape_typst(output.name.as_deref().unwrap_or("-")),
+        escape_typst(&output.animal),
+        decimals,
+        output.age,
+        decimals,
+        output.human_age,
+        decimals,
+        output.animal_progress * 100.0,
+        decimals,
+        output.human_progress * 100.0
+    )
+}
+
+/// Renders one element of the `results` of a pretty-printed JSON report,
+/// indented to sit inside it.
+pub fn render_array_item(conversion: &Conversion) -> String {
+    let json = serde_json::to_string_pretty(&Output::from(conversion)).unwrap();
+    json.lines()
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Writes a combined report for a batch of named pets in the requested format.
+pub fn print_report(
+    conversions: &[Conversion],
+    format: Format,
+    bars: &Bars,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    match format {
+        Format::Json => print_json_report(conversions, true, out)?,
+        Format::Csv => print_csv(conversions, true, out)?,
+        Format::Tsv => print_tsv(conversions, true, out)?,
+        Format::Ndjson
+        | Format::CsvLong
+        | Format::Yaml
+        | Format::Toml
+        | Format::Xml
+        | Format::Html
+        | Format::Latex
+        | Format::Typst => {
+            if let Some(header) = header(format) {
+                writeln!(out, "{}", header)?;
+            }
+            for conversion in conversions {
+                writeln!(out, "{}", render_line(conversion, format, bars))?;
+            }
+            if let Some(footer) = footer(format) {
+                writeln!(out, "{}", footer)?;
+            }
+        }
+        Format::Pdf => pdf::write_report(conversions, out)?,
+        Format::Svg => svg::write_chart(conversions, out)?,
+        Format::Ics => ics::write_calendar(conversions, out)?,
+        #[cfg(feature = "parquet")]
+        Format::Parquet => crate::parquet_file::write_file(conversions, out)?,
+        Format::Text => print_table(conversions, bars, out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// A borderless table, or a boxed one with `borders`, with the given columns
+/// right-aligned.
+pub fn new_table<const N: usize>(
+    borders: bool,
+    header: [&str; N],
+    right_aligned: &[usize],
+) -> Table {
+    let mut table = Table::new();
+    table
+        .load_style(if borders {
+            UTF8_FULL_CONDENSED
+        } else {
+            NOTHING
+        })
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(header);
+    for &idx in right_aligned {
+        if let Some(column) = table.column_mut(idx) {
+            column.set_cell_alignment(CellAlignment::Right);
+        }
+    }
+    table
+}
+
+/// Columns of the `--input` report table, and which of them are right-aligned.
+const TABLE_HEADER: [&str; 5] = ["Name", "Species", "Age", "Human age", "Lifespan"];
+const TABLE_RIGHT_ALIGNED: [bool; 5] = [false, false, true, true, true];
+
+/// The `--input` report table, with ages to `bars.decimals` places and
+/// percentages as the theme writes them.
+pub fn print_table(conversions: &[Conversion], bars: &Bars, out: &mut dyn Write) -> io::Result<()> {
+    let table = ReportTable::new(conversions, bars);
+    table.write_header(out)?;
+    for conversion in conversions {
+        table.write_row(conversion, out)?;
+    }
+    Ok(())
+}
+
+/// The report table written a row at a time, so a batch of any size streams
+/// under one header. Columns are as wide as the header and the rows it was
+/// sized from; a longer cell later on pushes the rest of its row right.
+pub struct ReportTable<'a> {
+    bars: &'a Bars,
+    widths: [usize; 5],
+}
+
+impl<'a> ReportTable<'a> {
+    pub fn new(sample: &[Conversion], bars: &'a Bars) -> ReportTable<'a> {
+        let mut widths = TABLE_HEADER.map(UnicodeWidthStr::width);
+        for conversion in sample {
+            for (width, cell) in widths.iter_mut().zip(table_cells(conversion, bars)) {
+                *width = (*width).max(cell.width());
+            }
+        }
+        ReportTable { bars, widths }
+    }
+
+    pub fn write_header(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.write_cells(TABLE_HEADER.map(String::from), out)
+    }
+
+    pub fn write_row(&self, conversion: &Conversion, out: &mut dyn Write) -> io::Result<()> {
+        self.write_cells(table_cells(conversion, self.bars), out)
+    }
+
+    fn write_cells(&self, cells: [String; 5], out: &mut dyn Write) -> io::Result<()> {
+        let mut line = String::new();
+        for ((cell, width), right) in cells.iter().zip(self.widths).zip(TABLE_RIGHT_ALIGNED) {
+            let pad = " ".repeat(width.saturating_sub(cell.width()));
+            if right {
+                line.push_str(&format!(" {}{} ", pad, cell));
+            } else {
+                line.push_str(&format!(" {}{} ", cell, pad));
+            }
+        }
+        writeln!(out, "{}", line)
+    }
+}
+
+fn table_cells(conversion: &Conversion, bars: &Bars) -> [String; 5] {
+    [
+        conversion.name.clone().unwrap_or_else(|| "-".to_string()),
+        conversion.label.clone(),
+        format!("{:.*}", bars.decimals, conversion.age),
+        format!("{:.*}", bars.decimals, conversion.human_age),
+        bars.theme
+            .percent(conversion.animal_progress())
+            .trim_start()
+            .to_string(),
+    ]
+}
+
+/// Characters in each progress bar of the comparison table.
+const TABLE_BAR_WIDTH: usize = 20;
+
+/// A progress bar cell in its animal's color. `age` and `typical` are fractions
+/// of the maximum lifespan; when the typical lifespan is shorter, the percentage
+/// is of it and the bar brackets the rest. `left` is the years of the typical
+/// lifespan still to go.
+fn bar_cell(age: f32, typical: f32, left: f32, paint: Paint, color: bool, bars: &Bars) -> Cell {
+    let progress = age / typical;
+    let width = bars.width.unwrap_or(TABLE_BAR_WIDTH);
+    let percent = format!("{} {}", bars.theme.percent(progress), years_left(left));
+    let bar = if typical < 1.0 {
+        bars.render_range(age, typical, width, None)
+    } else {
+        bars.render(progress, width)
+    };
+    let cell = Cell::new(format!("|{}| {}", bar, percent));
+    match paint.table_color().filter(|_| color) {
+        Some(fg) => cell.fg(fg),
+        None => cell,
+    }
+}
+
+/// An `--inline` cell: the animal's and its human equivalent's positions as two
+/// markers on one bar.
+fn inline_cell(conversion: &Conversion, color: bool, bars: &Bars) -> Cell {
+    let animal = conversion.animal_progress();
+    let human = conversion.human_age / conversion.human_max;
+    let width = bars.width.unwrap_or(TABLE_BAR_WIDTH);
+    Cell::new(format!(
+        "|{}| {} {} {} {}",
+        bars.render_inline(animal, human, width, color),
+        paint_mark(ANIMAL_MARK, color),
+        bars.theme.percent(animal),
+        paint_mark(HUMAN_MARK, color),
+        bars.theme.percent(human)
+    ))
+}
+
+/// Writes each animal's lifespan progress as sentences rather than bars, for
+/// screen readers and logs: how many years of its typical lifespan it has lived,
+/// and the same for its human equivalent.
+pub fn print_plain(conversions: &[Conversion], bars: &Bars, out: &mut dyn Write) -> io::Result<()> {
+    let percent = |progress: f32| {
+        let decimals = bars.theme.percent_decimals as usize;
+        format!("{:.*} percent", decimals, progress * 100.0)
+    };
+    // Spelled out, since a screen reader reads "~11 yrs" symbol by symbol.
+    let left = |years: f32| match years {
+        _ if years >= 1.5 => format!("about {:.0} years left", years),
+        _ if years >= 0.5 => "about 1 year left".to_string(),
+        _ if years > 0.0 => "less than a year left".to_string(),
+        _ => "past typical lifespan".to_string(),
+    };
+    for conversion in conversions {
+        let key = conversion.key();
+        writeln!(
+            out,
+            "{} years old {} is about {}.",
+            conversion.age,
+            conversion.label,
+            conversion.human_age_phrase(bars.decimals, false)
+        )?;
+        writeln!(
+            out,
+            "{}: {} of {} years, {} of typical lifespan, {}.",
+            key,
+            conversion.age,
+            conversion.animal_typical,
+            percent(conversion.animal_progress()),
+            left(conversion.animal_typical - conversion.age)
+        )?;
+        writeln!(
+            out,
+            "human equivalent: {:.*} of {} years, {} of lifespan, {}.",
+            bars.decimals,
+            conversion.human_age,
+            conversion.human_max,
+            percent(conversion.human_age / conversion.human_max),
+            left(conversion.human_max - conversion.human_age)
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes a legend of the species colors, one swatch per animal shown, when
+/// color is on.
+pub fn print_legend(
+    conversions: &[Conversion],
+    color: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    if !color {
+        return Ok(());
+    }
+    let mut keys: Vec<&str> = Vec::new();
+    let mut entries = Vec::new();
+    for conversion in conversions {
+        if keys.contains(&conversion.key()) {
+            continue;
+        }
+        keys.push(conversion.key());
+        let escape = species_paint(conversion.animal)
+            .escape()
+            .unwrap_or_default();
+        entries.push(format!(
+            "{}■{} {}",
+            escape,
+            crate::color::RESET,
+            conversion.key()
+        ));
+    }
+    writeln!(out, "{}", entries.join("  "))
+}
+
+/// Writes several animals side by side: one row each with their human age and
+/// progress bars through their own and a human lifespan, or one shared bar with
+/// `--inline`. Each animal's bars are in its species color.
+pub fn print_comparison(
+    conversions: &[Conversion],
+    borders: bool,
+    color: bool,
+    emoji: bool,
+    bars: &Bars,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut table = if bars.inline {
+        new_table(
+            borders,
+            ["Animal", "Age", "Human age", "Stage", "Lifespan"],
+            &[1, 2],
+        )
+    } else {
+        new_table(
+            borders,
+            [
+                "Animal",
+                "Age",
+                "Human age",
+                "Stage",
+                "Lifespan",
+                "Human lifespan",
+            ],
+            &[1, 2],
+        )
+    };
+    if color {
+        table.enforce_styling();
+    }
+    let label_style = bars.theme.label.attribute().filter(|_| color);
+    for conversion in conversions {
+        let label = Cell::new(if emoji {
+            format!("{} {}", conversion.animal.emoji(), conversion.key())
+        } else {
+            conversion.key().to_string()
+        });
+        let mut row = vec![
+            match label_style {
+                Some(style) => label.add_attribute(style),
+                None => label,
+            },
+            Cell::new(format!("{:.*}", bars.decimals, conversion.age)),
+            Cell::new(format!("{:.*}", bars.decimals, conversion.human_age)),
+            Cell::new(conversion.life_stage().key()),
+        ];
+        if bars.inline {
+            row.push(inline_cell(conversion, color, bars));
+            table.add_row(row);
+            continue;
+        }
+        let paint = species_paint(conversion.animal);
+        row.extend([
+            bar_cell(
+                conversion.age / conversion.animal_max,
+                conversion.animal_typical / conversion.animal_max,
+                conversion.animal_typical - conversion.age,
+                paint,
+                color,
+                bars,
+            ),
+            bar_cell(
+                conversion.human_age / conversion.human_max,
+                1.0,
+                conversion.human_max - conversion.human_age,
+                paint,
+                color,
+                bars,
+            ),
+        ]);
+        table.add_row(row);
+    }
+    writeln!(out, "{}", table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    #[test]
+    fn test_sort_highest_first() {
+        let mut pets = ["cat", "big_dog", "horse"].map(|animal| convert(animal, 8.0).unwrap());
+        SortKey::Progress.sort(&mut pets);
+        let keys: Vec<_> = pets.iter().map(|c| c.key()).collect();
+        assert_eq!(keys, ["big_dog", "cat", "horse"]);
+        SortKey::Lifespan.sort(&mut pets);
+        assert_eq!(pets[0].key(), "horse");
+    }
+
+    #[test]
+    fn test_tsv_line_replaces_separators() {
+        assert_eq!(
+            tsv_line(["Mr\tWhiskers", "cat", "3"]),
+            "Mr Whiskers\tcat\t3"
+        );
+        assert_eq!(tsv_line(["two\nlines"]), "two lines");
+        assert_eq!(header(Format::Tsv).unwrap(), CSV_HEADER.join("\t"));
+        assert!(header(Format::Tsv).unwrap().ends_with("\tlife_stage"));
+    }
+
+    #[test]
+    fn test_multi_animal_json_is_one_document() {
+        let pets = [convert("cat", 3.0).unwrap(), convert("horse", 3.0).unwrap()];
+        let mut out = Vec::new();
+        print_json_report(&pets, true, &mut out).unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(report["age"], 3.0);
+        assert_eq!(report["results"].as_array().unwrap().len(), 2);
+        assert_eq!(report["results"][1]["animal"], "horse");
+        assert_eq!(report["results"][1]["age"], 3.0);
+
+        // One animal gets the same shape.
+        let mut out = Vec::new();
+        print_json_report(&pets[..1], true, &mut out).unwrap();
+        let single: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(single["schema_version"], SCHEMA_VERSION);
+        assert_eq!(single["results"][0]["animal"], "cat");
+
+        // Results of different ages share none.
+        let pets = [
+            convert("cat", 3.0).unwrap(),
+            convert("horse", 10.0).unwrap(),
+        ];
+        let mut out = Vec::new();
+        print_json_report(&pets, false, &mut out).unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert!(report["age"].is_null());
+    }
+
+    #[test]
+    fn test_ndjson_report_is_one_object_per_line() {
+        let pets = [convert("cat", 3.0).unwrap(), convert("horse", 5.0).unwrap()];
+        let mut out = Vec::new();
+        print_report(&pets, Format::Ndjson, &Bars::default(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["animal"], "horse");
+    }
+
+    #[test]
+    fn test_csv_long_rows() {
+        let mut cat = convert("cat", 3.0).unwrap();
+        cat.name = Some("Tom".to_string());
+        let rows = csv_long_rows(&cat);
+        let rows: Vec<&str> = rows.lines().collect();
+        assert_eq!(rows.len(), CSV_HEADER.len() - 3);
+        assert_eq!(rows[0], "Tom,cat,age,3");
+        assert_eq!(rows[1], "Tom,cat,human_age,29");
+    }
+
+    #[test]
+    fn test_yaml_items_form_one_sequence() {
+        let items = [convert("cat", 3.0).unwrap(), convert("horse", 5.0).unwrap()]
+            .iter()
+            .map(yaml_item)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parsed: Vec<serde_yaml::Value> = serde_yaml::from_str(&items).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1]["animal"], "horse");
+    }
+
+    #[test]
+    fn test_toml_items_form_one_array() {
+        let items = [convert("cat", 3.0).unwrap(), convert("horse", 5.0).unwrap()]
+            .iter()
+            .map(toml_item)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parsed: toml::Table = items.parse().unwrap();
+        let results = parsed["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1]["animal"].as_str(), Some("horse"));
+    }
+
+    #[test]
+    fn test_xml_item() {
+        let mut cat = convert("cat", 3.0).unwrap();
+        cat.name = Some("Tom & Jerry".to_string());
+        let xml = xml_item(&cat);
+        assert!(xml.starts_with("  <result>\n    <name>Tom &amp; Jerry</name>\n"));
+        assert!(xml.contains("    <human_age>29</human_age>\n"));
+        assert!(xml.contains("        <early_rate>12.5</early_rate>\n"));
+        assert!(xml.ends_with("  </result>"));
+    }
+
+    #[test]
+    fn test_json_carries_warnings() {
+        let line = render_line(
+            &convert("cat", 3.0).unwrap(),
+            Format::Json,
+            &Bars::default(),
+        );
+        assert!(!line.contains("warnings"));
+        let line = render_line(
+            &convert("hamster", 5.0).unwrap(),
+            Format::Json,
+            &Bars::default(),
+        );
+        assert!(
+            line.ends_with(r#""warnings":["Age 5 exceeds typical hamster lifespan of 3 years."]}"#)
+        );
+        assert!(Format::Ndjson.embeds_warnings());
+        assert!(!Format::Csv.embeds_warnings());
+    }
+
+    #[test]
+    fn test_survive_to() {
+        let mut cat = convert("cat", 14.0).unwrap();
+        assert!(Output::from(&cat).survive_to.is_none());
+        cat.survive_to = Some(18.0);
+        // One in twenty cats reaches 18, so one in ten of those alive at 14.
+        assert_eq!(Output::from(&cat).survive_to.unwrap().probability, 0.1);
+        cat.survive_to = Some(12.0);
+        assert_eq!(Output::from(&cat).survive_to.unwrap().probability, 1.0);
+    }
+
+    /// Renders with fixed-width bars and no color, as when piped.
+    fn snapshot_bars() -> Bars {
+        Bars {
+            width: Some(10),
+            ..Bars::default()
+        }
+    }
+
+    #[test]
+    fn test_comparison_table_snapshot() {
+        let pets = ["cat", "big_dog", "hamster"].map(|animal| convert(animal, 2.5).unwrap());
+        let bars = Bars {
+            inline: true,
+            ..snapshot_bars()
+        };
+        let mut out = Vec::new();
+        print_comparison(&pets, true, false, false, &bars, &mut out).unwrap();
+        insta::assert_snapshot!(String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn test_comparison_past_human_lifespan() {
+        // A 90-year-old tortoise is past a human lifespan too: the bar stays
+        // full, but the percentage goes on past 100.
+        let pets = ["tortoise", "turtle"].map(|animal| convert(animal, 90.0).unwrap());
+        let mut out = Vec::new();
+        print_comparison(&pets, false, false, false, &snapshot_bars(), &mut out).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.contains("|==========| 120% past typical lifespan"));
+        assert!(table.contains("|==========| 268% past typical lifespan"));
+    }
+
+    #[test]
+    fn test_plain_snapshot() {
+        let pets = ["cat", "hamster"].map(|animal| convert(animal, 2.5).unwrap());
+        let mut out = Vec::new();
+        print_plain(&pets, &snapshot_bars(), &mut out).unwrap();
+        insta::assert_snapshot!(String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn test_json_snapshot() {
+        let mut cat = convert("cat", 3.0).unwrap();
+        cat.name = Some("Whiskers".to_string());
+        cat.survive_to = Some(15.0);
+        let mut out = Vec::new();
+        print_json(&cat, true, &mut out).unwrap();
+        insta::assert_snapshot!(String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn test_human_age_in_months() {
+        let kitten = convert("cat", 0.1).unwrap();
+        assert_eq!(Output::from(&kitten).human_age_months, Some(15.0));
+        assert_eq!(kitten.human_age_phrase(1, true), "15 human months (14–16)");
+        let cat = convert("cat", 3.0).unwrap();
+        assert!(Output::from(&cat).human_age_months.is_none());
+        assert_eq!(cat.human_age_phrase(1, false), "29.0 human years");
+    }
+
+    #[test]
+    fn test_progress_is_of_typical_lifespan() {
+        // 16 of a cat's typical 14 years, as the text bars show it.
+        let cat = convert("cat", 16.0).unwrap();
+        let output = Output::from(&cat);
+        assert!((output.animal_progress - 16.0 / 14.0).abs() < 1e-6);
+        assert!(html_row(&cat, &Bars::default()).contains(r#"title="100%""#));
+        let mut table = Vec::new();
+        print_table(&[cat], &Bars::default(), &mut table).unwrap();
+        assert!(String::from_utf8(table).unwrap().contains("114%"));
+    }
+
+    #[test]
+    fn test_text_line_uses_precision() {
+        let cat = convert("cat", 3.0).unwrap();
+        let bars = Bars {
+            decimals: 2,
+            ..Bars::default()
+        };
+        assert_eq!(
+            render_line(&cat, Format::Text, &bars),
+            "3 years old cat ≈ 29.00 human years"
+        );
+    }
+
+    #[test]
+    fn test_table_follows_precision() {
+        let mut bars = Bars {
+            decimals: 3,
+            ..Bars::default()
+        };
+        bars.theme.percent_decimals = 3;
+        let mut table = Vec::new();
+        print_table(&[convert("hamster", 0.33).unwrap()], &bars, &mut table).unwrap();
+        let table = String::from_utf8(table).unwrap();
+        assert!(table.contains(" 0.330 "), "{}", table);
+        assert!(table.contains("16.500%"), "{}", table);
+    }
+
+    #[test]
+    fn test_report_table_keeps_its_widths() {
+        let bars = Bars::default();
+        let table = ReportTable::new(&[convert("cat", 3.0).unwrap()], &bars);
+        let mut out = Vec::new();
+        table.write_header(&mut out).unwrap();
+        table
+            .write_row(&convert("horse", 20.0).unwrap(), &mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            " Name  Species  Age  Human age  Lifespan \n \
+             -     horse    20.0       86.5       74% \n"
+        );
+    }
+
+    #[test]
+    fn test_html_row() {
+        let mut cat = convert("cat", 12.0).unwrap();
+        cat.name = Some("<Tom>".to_string());
+        let row = html_row(&cat, &Bars::default());
+        assert!(row.starts_with("<tr><td>&lt;Tom&gt;</td><td>cat</td>"));
+        assert!(row.contains(r#"class="high" style="width: 85.7%""#));
+        let bars = Bars {
+            thresholds: "90,95".parse().unwrap(),
+            decimals: 3,
+            ..Bars::default()
+        };
+        let row = html_row(&cat, &bars);
+        assert!(row.contains(r#"class="low" style="width: 85.7%""#));
+        assert!(row.contains(r#"<td class="num">12.000</td>"#));
+    }
+
+    #[test]
+    fn test_plain_describes_lifespans_in_words() {
+        let mut out = Vec::new();
+        print_plain(&[convert("cat", 3.0).unwrap()], &Bars::default(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "3 years old cat is about 29.0 human years.\n\
+             cat: 3 of 14 years, 21 percent of typical lifespan, about 11 years left.\n\
+             human equivalent: 29.0 of 80 years, 36 percent of lifespan, about 51 years left.\n"
+        );
+        assert!(text.is_ascii());
+    }
+
+    #[test]
+    fn test_latex_and_typst_tables() {
+        let mut dog = convert("small_dog", 3.0).unwrap();
+        dog.name = Some("Rex & #1".to_string());
+        let mut out = Vec::new();
+        print_report(&[dog], Format::Latex, &Bars::default(), &mut out).unwrap();
+        let latex = String::from_utf8(out).unwrap();
+        assert!(latex.starts_with(r"\begin{tabular}{llrrrr}"));
+        assert!(latex.contains(r"Rex \& \#1 & small\_dog & 3.0 & 29.5 & 23.1 & 36.9 \\"));
+        assert!(latex.ends_with("\\end{tabular}\n"));
+
+        let row = typst_row(&convert("cat", 3.0).unwrap(), 1);
+        assert_eq!(row, "  [-], [cat], [3.0], [29.0], [21.4%], [36.2%],");
+    }
+}
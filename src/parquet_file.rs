@@ -0,0 +1,184 @@
+//! Apache Parquet export for `--format parquet`, with the JSON output's fields as
+//! columns. Batch results are written one row group at a time as they arrive.
+
+use crate::output::Output;
+use crate::{AppError, Conversion};
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType, FloatType};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Most rows buffered before they are written out as a row group.
+pub const ROW_GROUP_SIZE: usize = 8192;
+
+const SCHEMA: &str = "
+message animal_age {
+    OPTIONAL BYTE_ARRAY name (STRING);
+    REQUIRED BYTE_ARRAY animal (STRING);
+    OPTIONAL BYTE_ARRAY size_class (STRING);
+    REQUIRED FLOAT age;
+    REQUIRED FLOAT human_age;
+    REQUIRED FLOAT animal_max_lifespan;
+    OPTIONAL BYTE_ARRAY lifespan_method (STRING);
+    REQUIRED FLOAT human_max_lifespan;
+    REQUIRED FLOAT animal_progress;
+    REQUIRED FLOAT human_progress;
+    REQUIRED BYTE_ARRAY formula (STRING);
+}
+";
+
+fn parquet_error(err: parquet::errors::ParquetError) -> AppError {
+    AppError::Parquet(err.to_string())
+}
+
+/// One column's values, with definition levels for the optional columns.
+enum Column {
+    Text(Vec<ByteArray>),
+    OptionalText(Vec<ByteArray>, Vec<i16>),
+    Number(Vec<f32>),
+}
+
+impl Column {
+    fn text(rows: &[Output], field: impl Fn(&Output) -> &str) -> Column {
+        Column::Text(rows.iter().map(|row| field(row).into()).collect())
+    }
+
+    fn optional_text(rows: &[Output], field: impl Fn(&Output) -> Option<&str>) -> Column {
+        let values = rows.iter().filter_map(&field).map(ByteArray::from);
+        let levels = rows.iter().map(|row| field(row).is_some() as i16);
+        Column::OptionalText(values.collect(), levels.collect())
+    }
+
+    fn number(rows: &[Output], field: impl Fn(&Output) -> f32) -> Column {
+        Column::Number(rows.iter().map(field).collect())
+    }
+}
+
+/// A Parquet file being written to `out`, one row group per [`ParquetWriter::write`].
+pub struct ParquetWriter {
+    writer: SerializedFileWriter<Vec<u8>>,
+}
+
+impl ParquetWriter {
+    pub fn new() -> Result<Self, AppError> {
+        let schema = Arc::new(parse_message_type(SCHEMA).map_err(parquet_error)?);
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_created_by(format!("animal-age {}", env!("CARGO_PKG_VERSION")))
+            .build();
+        let writer = SerializedFileWriter::new(Vec::new(), schema, Arc::new(properties))
+            .map_err(parquet_error)?;
+        Ok(ParquetWriter { writer })
+    }
+
+    /// Writes `conversions` as one row group and passes the encoded bytes on to `out`.
+    pub fn write(
+        &mut self,
+        conversions: &[Conversion],
+        out: &mut dyn Write,
+    ) -> Result<(), AppError> {
+        if conversions.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<Output> = conversions.iter().map(Output::from).collect();
+        let columns = [
+            Column::optional_text(&rows, |row| row.name.as_deref()),
+            Column::text(&rows, |row| &row.animal),
+            Column::optional_text(&rows, |row| row.size_class.as_deref()),
+            Column::number(&rows, |row| row.age),
+            Column::number(&rows, |row| row.human_age),
+            Column::number(&rows, |row| row.animal_max_lifespan),
+            Column::optional_text(&rows, |row| row.lifespan_method.as_deref()),
+            Column::number(&rows, |row| row.human_max_lifespan),
+            Column::number(&rows, |row| row.animal_progress),
+            Column::number(&rows, |row| row.human_progress),
+            Column::text(&rows, |row| row.formula.name),
+        ];
+
+        let mut row_group = self.writer.next_row_group().map_err(parquet_error)?;
+        for column in columns {
+            let mut writer = row_group
+                .next_column()
+                .map_err(parquet_error)?
+                .expect("schema and columns have the same length");
+            match column {
+                Column::Text(values) => writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, None, None),
+                Column::OptionalText(values, levels) => writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, Some(&levels), None),
+                Column::Number(values) => {
+                    writer.typed::<FloatType>().write_batch(&values, None, None)
+                }
+            }
+            .map_err(parquet_error)?;
+            writer.close().map_err(parquet_error)?;
+        }
+        row_group.close().map_err(parquet_error)?;
+        self.drain(out)
+    }
+
+    /// Writes the footer that makes the file readable.
+    pub fn finish(mut self, out: &mut dyn Write) -> Result<(), AppError> {
+        self.writer.finish().map_err(parquet_error)?;
+        self.drain(out)
+    }
+
+    /// Moves everything encoded so far to `out`. The writer tracks file offsets
+    /// itself, so emptying its buffer does not disturb them.
+    fn drain(&mut self, out: &mut dyn Write) -> Result<(), AppError> {
+        self.writer.flush()?;
+        out.write_all(&std::mem::take(self.writer.inner_mut()))?;
+        Ok(())
+    }
+}
+
+/// Writes `conversions` as a complete Parquet file.
+pub fn write_file(conversions: &[Conversion], out: &mut dyn Write) -> Result<(), AppError> {
+    let mut writer = ParquetWriter::new()?;
+    for chunk in conversions.chunks(ROW_GROUP_SIZE) {
+        writer.write(chunk, out)?;
+    }
+    writer.finish(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use std::fs::{self, File};
+
+    #[test]
+    fn test_row_groups_read_back() {
+        let mut cat = convert("cat", 3.0).unwrap();
+        cat.name = Some("Whiskers".to_string());
+        let pets = [cat, convert("horse", 10.0).unwrap()];
+
+        let mut bytes = Vec::new();
+        let mut writer = ParquetWriter::new().unwrap();
+        writer.write(&pets[..1], &mut bytes).unwrap();
+        writer.write(&pets[1..], &mut bytes).unwrap();
+        writer.finish(&mut bytes).unwrap();
+
+        let path = std::env::temp_dir().join(format!("animal-age-{}.parquet", std::process::id()));
+        fs::write(&path, &bytes).unwrap();
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 2);
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), 11);
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap().to_string())
+            .collect();
+        assert!(rows[0].contains(r#"name: "Whiskers""#));
+        assert!(rows[1].contains("human_age: 46.5"));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,258 @@
+//! Strict, panic-free parsers for untrusted user input.
+//!
+//! Every parser rejects input longer than [`MAX_INPUT_LEN`] bytes before doing any
+//! other work, never indexes or unwraps, and allocates at most a bounded amount, so
+//! embedders can feed them arbitrary data (and fuzzers can hammer them).
+
+pub use crate::animal::MAX_AGE_YEARS;
+use crate::animal::{Animal, Category};
+pub use crate::date::Date;
+use crate::date::DAYS_PER_YEAR;
+pub use crate::weight::Weight;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// Longest input, in bytes, that any parser will look at.
+pub const MAX_INPUT_LEN: usize = 256;
+
+/// The name that stands for every supported animal.
+pub const ALL_ANIMALS: &str = "all";
+
+/// Most animals a single comma-separated spec may expand to: enough for
+/// `all` several times over.
+pub const MAX_ANIMALS: usize = 4 * Animal::ALL.len();
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("input is empty")]
+    Empty,
+    #[error("input exceeds {MAX_INPUT_LEN} bytes")]
+    TooLong,
+    #[error("unknown animal type: {0}")]
+    UnknownAnimal(String),
+    #[error("ambiguous animal type: {0} could be {}", .1.join(", "))]
+    AmbiguousAnimal(String, Vec<String>),
+    #[error("too many animals (at most {MAX_ANIMALS})")]
+    TooManyAnimals,
+    #[error("invalid number")]
+    InvalidNumber,
+    #[error("unknown age unit: {0}")]
+    UnknownUnit(String),
+    #[error("age must be between 0 and {MAX_AGE_YEARS} years")]
+    AgeOutOfRange,
+    #[error("invalid date, expected YYYY-MM-DD")]
+    InvalidDate,
+    #[error("invalid range, expected LOW..HIGH with LOW <= HIGH")]
+    InvalidRange,
+    #[error("expected `animal,age`")]
+    InvalidRecord,
+    #[error("invalid weight, expected e.g. `28kg` or `60lb`")]
+    InvalidWeight,
+}
+
+fn bounded(input: &str) -> Result<&str, ParseError> {
+    if input.len() > MAX_INPUT_LEN {
+        return Err(ParseError::TooLong);
+    }
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(trimmed)
+}
+
+/// Parses a single animal the way `-t` takes it: a key or alias such as `cat`,
+/// `Small_Dog`, or `kitten`, or the start of exactly one key, such as `ham`.
+pub fn parse_animal(input: &str) -> Result<Animal, ParseError> {
+    let name = bounded(input)?;
+    if let Some(animal) = Animal::from_key(name) {
+        return Ok(animal);
+    }
+    match Animal::with_prefix(name).as_slice() {
+        [animal] => Ok(*animal),
+        [] => Err(ParseError::UnknownAnimal(name.to_string())),
+        candidates => Err(ParseError::AmbiguousAnimal(
+            name.to_string(),
+            candidates.iter().map(|a| a.key().to_string()).collect(),
+        )),
+    }
+}
+
+/// The animals a group name stands for: every one for [`ALL_ANIMALS`], or the
+/// members of a category such as `rodents`, in display order. `None` for any
+/// other name.
+pub fn parse_group(input: &str) -> Option<Vec<Animal>> {
+    let name = bounded(input).ok()?;
+    if name.eq_ignore_ascii_case(ALL_ANIMALS) {
+        return Some(Animal::ALL.to_vec());
+    }
+    Category::from_key(name).map(|category| category.members())
+}
+
+/// Parses a comma-separated animal list such as `cat,small_dog`, where each
+/// part is an animal as [`parse_animal`] takes it or a group as
+/// [`parse_group`] expands it.
+pub fn parse_animal_spec(input: &str) -> Result<Vec<Animal>, ParseError> {
+    let spec = bounded(input)?;
+    let mut animals = Vec::new();
+    for part in spec.split(',') {
+        let members = match parse_group(part) {
+            Some(members) => members,
+            None => vec![parse_animal(part)?],
+        };
+        if animals.len() + members.len() > MAX_ANIMALS {
+            return Err(ParseError::TooManyAnimals);
+        }
+        animals.extend(members);
+    }
+    Ok(animals)
+}
+
+/// Parses an age with an optional unit (`3`, `3.5y`, `18 months`, `6w`, `10d`) into years.
+pub fn parse_age(input: &str) -> Result<f32, ParseError> {
+    let age = bounded(input)?;
+    let split = age.find(|c: char| c.is_alphabetic()).unwrap_or(age.len());
+    let (number, unit) = age.split_at(split);
+
+    let number = number.trim();
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return Err(ParseError::InvalidNumber);
+    }
+    let value: f32 = number.parse().map_err(|_| ParseError::InvalidNumber)?;
+
+    let years = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "y" | "yr" | "yrs" | "year" | "years" => value,
+        "mo" | "mos" | "month" | "months" => value / 12.0,
+        "w" | "wk" | "wks" | "week" | "weeks" => value * 7.0 / DAYS_PER_YEAR,
+        "d" | "day" | "days" => value / DAYS_PER_YEAR,
+        other => return Err(ParseError::UnknownUnit(other.to_string())),
+    };
+
+    if !years.is_finite() || !(0.0..=MAX_AGE_YEARS).contains(&years) {
+        return Err(ParseError::AgeOutOfRange);
+    }
+    Ok(years)
+}
+
+/// Parses an ISO-8601 calendar date (`YYYY-MM-DD`).
+pub fn parse_date(input: &str) -> Result<Date, ParseError> {
+    bounded(input)?.parse().map_err(|_| ParseError::InvalidDate)
+}
+
+/// Parses a body weight such as `28kg`, `60 lb`, or a bare number of kilograms.
+pub fn parse_weight(input: &str) -> Result<Weight, ParseError> {
+    bounded(input)?
+        .parse()
+        .map_err(|_| ParseError::InvalidWeight)
+}
+
+/// Parses an age range such as `1..5` or `6mo..2y` into years.
+pub fn parse_range(input: &str) -> Result<RangeInclusive<f32>, ParseError> {
+    let range = bounded(input)?;
+    let (low, high) = range.split_once("..").ok_or(ParseError::InvalidRange)?;
+    let low = parse_age(low)?;
+    let high = parse_age(high)?;
+    if low > high {
+        return Err(ParseError::InvalidRange);
+    }
+    Ok(low..=high)
+}
+
+/// Parses an `animal,age` record as used by batch input.
+pub fn parse_record(input: &str) -> Result<(Animal, f32), ParseError> {
+    let record = bounded(input)?;
+    let (animal, age) = record.split_once(',').ok_or(ParseError::InvalidRecord)?;
+    Ok((parse_animal(animal)?, parse_age(age)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_animal_spec() {
+        assert_eq!(
+            parse_animal_spec("cat, Small_Dog"),
+            Ok(vec![Animal::Cat, Animal::SmallDog])
+        );
+        assert_eq!(parse_animal_spec("cat,,hamster"), Err(ParseError::Empty));
+        assert_eq!(parse_animal("ham"), Ok(Animal::Hamster));
+        assert_eq!(
+            parse_animal("go"),
+            Err(ParseError::AmbiguousAnimal(
+                "go".to_string(),
+                vec!["goldfish".to_string(), "goat".to_string()]
+            ))
+        );
+        assert_eq!(
+            parse_animal_spec("rodents").unwrap(),
+            Category::Rodent.members()
+        );
+        assert_eq!(parse_animal_spec("ALL").unwrap(), Animal::ALL);
+        assert_eq!(
+            parse_animal_spec(&[ALL_ANIMALS; 5].join(",")),
+            Err(ParseError::TooManyAnimals)
+        );
+    }
+
+    #[test]
+    fn test_parse_age_units() {
+        assert_eq!(parse_age("3"), Ok(3.0));
+        assert_eq!(parse_age("3.5 years"), Ok(3.5));
+        assert_eq!(parse_age("18mo"), Ok(1.5));
+        assert_eq!(parse_age("365.25d"), Ok(1.0));
+        assert!(parse_age("3 parsecs").is_err());
+        assert!(parse_age("-1").is_err());
+        assert!(parse_age("inf").is_err());
+        assert!(parse_age("NaN").is_err());
+        assert!(parse_age("1e9").is_err());
+        assert_eq!(parse_age("201"), Err(ParseError::AgeOutOfRange));
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("2020-02-29"),
+            Ok(Date {
+                year: 2020,
+                month: 2,
+                day: 29
+            })
+        );
+        assert!(parse_date("2021-02-29").is_err());
+        assert!(parse_date("2021-13-01").is_err());
+        assert!(parse_date("2021-1-01").is_err());
+        assert!(parse_date("+021-01-01").is_err());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("6mo..2y"), Ok(0.5..=2.0));
+        assert_eq!(parse_range("5..1"), Err(ParseError::InvalidRange));
+        assert_eq!(parse_range("1-5"), Err(ParseError::InvalidRange));
+    }
+
+    #[test]
+    fn test_rejects_oversized_and_hostile_input() {
+        let long = "9".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(parse_age(&long), Err(ParseError::TooLong));
+        assert_eq!(parse_record(&long), Err(ParseError::TooLong));
+        for input in [
+            "",
+            " ",
+            ",",
+            "..",
+            "\u{0}",
+            "🐱,3",
+            "cat,\u{202e}3",
+            "0000-00-00",
+        ] {
+            let _ = parse_animal_spec(input);
+            let _ = parse_age(input);
+            let _ = parse_date(input);
+            let _ = parse_range(input);
+            let _ = parse_record(input);
+            let _ = parse_weight(input);
+        }
+    }
+}
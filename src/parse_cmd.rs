@@ -0,0 +1,146 @@
+//! The `animal-age parse` subcommand: runs the strict parsers against literal
+//! inputs, or against a fuzz-style corpus with `--check`.
+
+use crate::AppError;
+use animal_age::parse::{self, ParseError, MAX_INPUT_LEN};
+use clap::ValueEnum;
+use std::fs;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub struct ParseArgs {
+    /// Parser to run
+    #[arg(long, value_enum, default_value_t = ParseKind::All)]
+    kind: ParseKind,
+
+    /// Treat INPUTS as corpus files or directories and report any parser panics
+    #[arg(long)]
+    check: bool,
+
+    /// Inputs to parse (corpus files or directories with --check)
+    #[arg(required = true, value_name = "INPUTS")]
+    inputs: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ParseKind {
+    All,
+    Animal,
+    Age,
+    Date,
+    Range,
+    Record,
+    Weight,
+}
+
+impl ParseKind {
+    const PARSERS: [ParseKind; 6] = [
+        ParseKind::Animal,
+        ParseKind::Age,
+        ParseKind::Date,
+        ParseKind::Range,
+        ParseKind::Record,
+        ParseKind::Weight,
+    ];
+
+    fn selected(self) -> Vec<ParseKind> {
+        match self {
+            ParseKind::All => Self::PARSERS.to_vec(),
+            kind => vec![kind],
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ParseKind::All => "all",
+            ParseKind::Animal => "animal",
+            ParseKind::Age => "age",
+            ParseKind::Date => "date",
+            ParseKind::Range => "range",
+            ParseKind::Record => "record",
+            ParseKind::Weight => "weight",
+        }
+    }
+
+    fn run(self, input: &str) -> Result<String, ParseError> {
+        match self {
+            ParseKind::All => unreachable!("`all` is expanded by selected()"),
+            ParseKind::Animal => parse::parse_animal_spec(input).map(|a| format!("{:?}", a)),
+            ParseKind::Age => parse::parse_age(input).map(|age| format!("{} years", age)),
+            ParseKind::Date => parse::parse_date(input).map(|d| format!("{:?}", d)),
+            ParseKind::Range => parse::parse_range(input).map(|r| format!("{:?}", r)),
+            ParseKind::Record => parse::parse_record(input).map(|r| format!("{:?}", r)),
+            ParseKind::Weight => parse::parse_weight(input).map(|w| w.to_string()),
+        }
+    }
+}
+
+pub fn run(args: &ParseArgs) -> Result<(), AppError> {
+    if args.check {
+        return check_corpus(args);
+    }
+
+    for input in &args.inputs {
+        for kind in args.kind.selected() {
+            match kind.run(input) {
+                Ok(parsed) => println!("{:6} {:?}: ok {}", kind.name(), input, parsed),
+                Err(err) => println!("{:6} {:?}: error: {}", kind.name(), input, err),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_corpus(args: &ParseArgs) -> Result<(), AppError> {
+    let mut files = Vec::new();
+    for input in &args.inputs {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            entries.retain(|p| p.is_file());
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    let (mut accepted, mut rejected, mut panicked) = (0, 0, 0);
+    for file in &files {
+        // Read one byte past the limit so oversized inputs still hit the TooLong path
+        // without loading arbitrarily large files.
+        let mut bytes = Vec::new();
+        fs::File::open(file)?
+            .take(MAX_INPUT_LEN as u64 + 1)
+            .read_to_end(&mut bytes)?;
+        let input = String::from_utf8_lossy(&bytes);
+
+        for kind in args.kind.selected() {
+            match panic::catch_unwind(AssertUnwindSafe(|| kind.run(&input))) {
+                Ok(Ok(_)) => accepted += 1,
+                Ok(Err(_)) => rejected += 1,
+                Err(_) => {
+                    panicked += 1;
+                    eprintln!("{} parser panicked on {}", kind.name(), file.display());
+                }
+            }
+        }
+    }
+
+    println!(
+        "Checked {} inputs: {} accepted, {} rejected, {} panicked",
+        files.len(),
+        accepted,
+        rejected,
+        panicked
+    );
+
+    if panicked > 0 {
+        return Err(AppError::ParserPanic(panicked));
+    }
+    Ok(())
+}
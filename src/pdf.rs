@@ -0,0 +1,202 @@
+//! Printable PDF reports: one A4 page per pet, for `--output report.pdf`.
+
+use crate::{AppError, Conversion};
+use animal_age::date::Date;
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Rect, Rgb,
+};
+use std::io::{BufWriter, Write};
+
+const PAGE_WIDTH: Mm = Mm(210.0);
+const PAGE_HEIGHT: Mm = Mm(297.0);
+const MARGIN: f32 = 20.0;
+const BAR_WIDTH: f32 = 120.0;
+const BAR_HEIGHT: f32 = 8.0;
+
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+/// Writes a report with one page per conversion to `out`.
+pub fn write_report(conversions: &[Conversion], out: &mut dyn Write) -> Result<(), AppError> {
+    let (doc, first_page, first_layer) =
+        PdfDocument::new("animal-age report", PAGE_WIDTH, PAGE_HEIGHT, "Report");
+    let fonts = Fonts {
+        regular: doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(pdf_error)?,
+        bold: doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(pdf_error)?,
+    };
+    let today = Date::today();
+
+    for (idx, conversion) in conversions.iter().enumerate() {
+        let (page, layer) = if idx == 0 {
+            (first_page, first_layer)
+        } else {
+            doc.add_page(PAGE_WIDTH, PAGE_HEIGHT, "Report")
+        };
+        draw_page(
+            doc.get_page(page).get_layer(layer),
+            &fonts,
+            conversion,
+            today,
+        );
+    }
+
+    doc.save(&mut BufWriter::new(out)).map_err(pdf_error)
+}
+
+fn pdf_error(err: printpdf::Error) -> AppError {
+    AppError::Pdf(err.to_string())
+}
+
+/// A page being laid out from the top margin down.
+struct Page<'a> {
+    layer: PdfLayerReference,
+    fonts: &'a Fonts,
+    y: f32,
+}
+
+impl Page<'_> {
+    fn line(&mut self, text: &str, size: f32, bold: bool) {
+        let font = if bold {
+            &self.fonts.bold
+        } else {
+            &self.fonts.regular
+        };
+        self.layer
+            .use_text(text, size, Mm(MARGIN), Mm(self.y), font);
+        self.y -= size * 0.6;
+    }
+
+    fn gap(&mut self, mm: f32) {
+        self.y -= mm;
+    }
+}
+
+fn draw_page(layer: PdfLayerReference, fonts: &Fonts, conversion: &Conversion, today: Date) {
+    let mut page = Page {
+        layer,
+        fonts,
+        y: PAGE_HEIGHT.0 - MARGIN - 10.0,
+    };
+
+    page.line(
+        conversion.name.as_deref().unwrap_or(&conversion.label),
+        28.0,
+        true,
+    );
+    let species = match conversion.breed {
+        Some(breed) => format!("{} ({})", breed.name, conversion.animal.key()),
+        None => conversion.label.clone(),
+    };
+    page.line(
+        &format!("{}, {:.1} years old", species, conversion.age),
+        14.0,
+        false,
+    );
+    page.gap(6.0);
+    page.line(
+        &format!("About {:.1} in human years", conversion.human_age),
+        20.0,
+        true,
+    );
+    page.line(
+        &format!("Typical lifespan: {} years", conversion.animal_typical),
+        12.0,
+        false,
+    );
+    page.gap(10.0);
+
+    for (label, value, max) in [
+        (
+            format!("{} lifespan", conversion.label),
+            conversion.age,
+            conversion.animal_typical,
+        ),
+        (
+            "Human lifespan".to_string(),
+            conversion.human_age,
+            conversion.human_max,
+        ),
+    ] {
+        page.line(
+            &format!("{}: {:.0}%", label, value / max * 100.0),
+            12.0,
+            false,
+        );
+        draw_bar(&page.layer, page.y - BAR_HEIGHT + 2.0, value / max);
+        page.gap(BAR_HEIGHT + 10.0);
+    }
+
+    for warning in &conversion.warnings {
+        page.line(&format!("Note: {}", warning), 11.0, false);
+    }
+
+    page.layer.set_fill_color(grey(0.4));
+    page.layer.use_text(
+        format!("Generated by animal-age on {}", today),
+        9.0,
+        Mm(MARGIN),
+        Mm(MARGIN),
+        &fonts.regular,
+    );
+}
+
+/// A grey track with a filled portion, colored with the terminal bar thresholds.
+fn draw_bar(layer: &PdfLayerReference, y: f32, progress: f32) {
+    let progress = progress.clamp(0.0, 1.0);
+    layer.set_fill_color(grey(0.9));
+    layer.add_rect(Rect::new(
+        Mm(MARGIN),
+        Mm(y),
+        Mm(MARGIN + BAR_WIDTH),
+        Mm(y + BAR_HEIGHT),
+    ));
+
+    let fill = if progress >= 0.8 {
+        rgb(0.75, 0.22, 0.17)
+    } else if progress >= 0.6 {
+        rgb(0.83, 0.63, 0.09)
+    } else {
+        rgb(0.16, 0.63, 0.6)
+    };
+    layer.set_fill_color(fill);
+    layer.add_rect(Rect::new(
+        Mm(MARGIN),
+        Mm(y),
+        Mm(MARGIN + BAR_WIDTH * progress),
+        Mm(y + BAR_HEIGHT),
+    ));
+    layer.set_fill_color(grey(0.0));
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+fn grey(level: f32) -> Color {
+    rgb(level, level, level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    #[test]
+    fn test_one_page_per_pet() {
+        let pets = [
+            convert("cat", 3.0).unwrap(),
+            convert("horse", 40.0).unwrap(),
+        ];
+        let mut pdf = Vec::new();
+        write_report(&pets, &mut pdf).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 2"));
+    }
+}
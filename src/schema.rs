@@ -0,0 +1,194 @@
+//! JSON Schema for the JSON output, printed by `animal-age schema`.
+
+use crate::output::SCHEMA_VERSION;
+use serde_json::{json, Value};
+
+/// Describes every JSON document the tool writes: the report `--format json`
+/// writes for any number of animals, or one line of NDJSON.
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!(
+            "https://github.com/fibnas/animal-age/schema/v{}.json",
+            SCHEMA_VERSION
+        ),
+        "title": "animal-age output",
+        "oneOf": [
+            { "$ref": "#/$defs/report" },
+            { "$ref": "#/$defs/versioned_result" }
+        ],
+        "$defs": {
+            "schema_version": {
+                "description": "Version of this schema; bumped on breaking changes",
+                "const": SCHEMA_VERSION
+            },
+            "result": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Pet name, from batch input" },
+                    "animal": { "type": "string", "description": "Animal type or breed key" },
+                    "size_class": {
+                        "type": "string",
+                        "description": "Dog size class whose formula was used, for dog breeds"
+                    },
+                    "sex": {
+                        "enum": ["male", "female"],
+                        "description": "Sex the lifespans were adjusted for"
+                    },
+                    "neutered": {
+                        "const": true,
+                        "description": "Lifespans were lengthened for a neutered animal; absent otherwise"
+                    },
+                    "age": { "type": "number", "minimum": 0 },
+                    "human_age": { "type": "number", "minimum": 0 },
+                    "human_age_low": {
+                        "type": "number",
+                        "minimum": 0,
+                        "description": "Low end of the range human_age likely falls in"
+                    },
+                    "human_age_high": {
+                        "type": "number",
+                        "minimum": 0,
+                        "description": "High end of the range human_age likely falls in"
+                    },
+                    "human_age_months": {
+                        "type": "number",
+                        "minimum": 0,
+                        "description": "human_age in months; present only when it is under two years"
+                    },
+                    "animal_typical_lifespan": {
+                        "type": "number",
+                        "exclusiveMinimum": 0,
+                        "description": "How long the animal usually lives"
+                    },
+                    "animal_max_lifespan": { "type": "number", "exclusiveMinimum": 0 },
+                    "lifespan_method": {
+                        "type": "string",
+                        "description": "How animal_max_lifespan was estimated, when not from a table"
+                    },
+                    "human_max_lifespan": { "type": "number", "exclusiveMinimum": 0 },
+                    "animal_progress": {
+                        "type": "number",
+                        "minimum": 0,
+                        "description": "age as a share of animal_typical_lifespan; past 1 once the animal outlives it"
+                    },
+                    "human_progress": { "type": "number", "minimum": 0 },
+                    "age_percentile": {
+                        "type": "number",
+                        "minimum": 0,
+                        "maximum": 100,
+                        "description": "Percentage of animals of this kind that die younger than age"
+                    },
+                    "aging_rate": {
+                        "type": "number",
+                        "minimum": 0,
+                        "description": "Human years per calendar year at age, the formula's slope there"
+                    },
+                    "life_stage": {
+                        "enum": ["baby", "juvenile", "adult", "mature", "senior", "geriatric"],
+                        "description": "Stage of life the animal is in at age"
+                    },
+                    "survive_to": {
+                        "type": "object",
+                        "description": "Chance of living to the age given with --survive-to",
+                        "properties": {
+                            "age": { "type": "number", "minimum": 0 },
+                            "probability": { "type": "number", "minimum": 0, "maximum": 1 }
+                        },
+                        "required": ["age", "probability"]
+                    },
+                    "formula": { "$ref": "#/$defs/formula" },
+                    "warnings": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Warnings about the conversion; absent when there are none"
+                    }
+                },
+                "required": [
+                    "animal",
+                    "age",
+                    "human_age",
+                    "human_age_low",
+                    "human_age_high",
+                    "animal_typical_lifespan",
+                    "animal_max_lifespan",
+                    "human_max_lifespan",
+                    "animal_progress",
+                    "human_progress",
+                    "age_percentile",
+                    "aging_rate",
+                    "life_stage",
+                    "formula"
+                ]
+            },
+            "formula": {
+                "type": "object",
+                "description": "The conversion model that produced human_age",
+                "properties": {
+                    "name": { "enum": ["linear", "two_stage", "three_stage", "logarithmic"] },
+                    "standard": { "type": "string" },
+                    "parameters": {
+                        "type": "object",
+                        "additionalProperties": { "type": "number" }
+                    },
+                    "source": { "type": "string" }
+                },
+                "required": ["name", "standard", "parameters", "source"]
+            },
+            "versioned_result": {
+                "description": "One line of NDJSON output",
+                "allOf": [{ "$ref": "#/$defs/result" }],
+                "properties": { "schema_version": { "$ref": "#/$defs/schema_version" } },
+                "required": ["schema_version"]
+            },
+            "report": {
+                "type": "object",
+                "description": "The --format json output, for one animal, several, or a batch",
+                "properties": {
+                    "schema_version": { "$ref": "#/$defs/schema_version" },
+                    "version": { "type": "string", "description": "animal-age version" },
+                    "results": { "type": "array", "items": { "$ref": "#/$defs/result" } },
+                    "age": {
+                        "type": ["number", "null"],
+                        "description": "Age in years every result shares, or null when they differ"
+                    }
+                },
+                "required": ["schema_version", "version", "results", "age"]
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, convert_breed, output};
+
+    #[test]
+    fn test_schema_covers_every_output_field() {
+        let schema = json_schema();
+        let properties = schema["$defs"]["result"]["properties"].as_object().unwrap();
+        let mut named = convert_breed("beagle", 3.0).unwrap();
+        named.name = Some("Rex".to_string());
+
+        let mut out = Vec::new();
+        output::print_json(&named, false, &mut out).unwrap();
+        let result: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(result["schema_version"], SCHEMA_VERSION);
+        for field in result.as_object().unwrap().keys() {
+            assert!(
+                field == "schema_version" || properties.contains_key(field),
+                "{} is missing from the schema",
+                field
+            );
+        }
+
+        let pets = [convert("cat", 3.0).unwrap(), convert("horse", 3.0).unwrap()];
+        let mut out = Vec::new();
+        output::print_json_report(&pets, false, &mut out).unwrap();
+        let report: Value = serde_json::from_slice(&out).unwrap();
+        for field in schema["$defs"]["report"]["required"].as_array().unwrap() {
+            assert!(report.get(field.as_str().unwrap()).is_some());
+        }
+    }
+}
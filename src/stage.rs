@@ -0,0 +1,109 @@
+//! Life stages for each species, with the thresholds overridden in the config
+//! file.
+//!
+//! ```toml
+//! [life_stages.cat]
+//! senior = 10.0
+//!
+//! [life_stages.dog]    # every dog size class
+//! geriatric = 11.0
+//! ```
+
+use crate::config::Config;
+use crate::{AppError, Conversion};
+use animal_age::animal::LifeStage;
+use std::collections::BTreeMap;
+
+/// Overridden life stage thresholds, applied on top of the species data.
+#[derive(Default)]
+pub struct Stages {
+    /// Ages keyed by animal key, or `dog` for every dog, then by stage name.
+    overrides: BTreeMap<String, BTreeMap<String, f32>>,
+}
+
+impl Stages {
+    pub fn from_config(config: &Config) -> Stages {
+        Stages {
+            overrides: config.life_stages.clone(),
+        }
+    }
+
+    /// Moves the start of the stages overridden for `conversion`'s animal. An
+    /// override for the animal's own key wins over one for every `dog`.
+    pub fn apply(&self, conversion: &mut Conversion) -> Result<(), AppError> {
+        let animal = conversion.animal;
+        let keys = [Some("dog").filter(|_| animal.is_dog()), Some(animal.key())];
+        let mut stages = conversion.life_stages;
+        for overrides in keys
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.overrides.get(key))
+        {
+            for (name, &years) in overrides {
+                let stage = name.parse::<LifeStage>().map_err(AppError::InvalidInput)?;
+                stages.set_start(stage, years).map_err(|err| {
+                    AppError::InvalidInput(format!("{} life stages: {}", animal.key(), err))
+                })?;
+            }
+        }
+        if !stages.is_ordered() {
+            return Err(AppError::InvalidInput(format!(
+                "{} life stages must start in order: {}",
+                animal.key(),
+                LifeStage::ALL.map(|stage| stage.key()).join(", ")
+            )));
+        }
+        conversion.life_stages = stages;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    fn stages(toml: &str) -> Stages {
+        Stages::from_config(&toml::from_str(toml).unwrap())
+    }
+
+    #[test]
+    fn test_overrides() {
+        let stages = stages(
+            r#"
+            [life_stages.cat]
+            senior = 10.0
+
+            [life_stages.dog]
+            senior = 7.0
+
+            [life_stages.big_dog]
+            senior = 5.0
+            "#,
+        );
+        let mut cat = convert("cat", 10.5).unwrap();
+        assert_eq!(cat.life_stage(), LifeStage::Mature);
+        stages.apply(&mut cat).unwrap();
+        assert_eq!(cat.life_stage(), LifeStage::Senior);
+
+        let mut small = convert("small_dog", 7.5).unwrap();
+        stages.apply(&mut small).unwrap();
+        assert_eq!(small.life_stage(), LifeStage::Senior);
+        let mut big = convert("big_dog", 5.5).unwrap();
+        stages.apply(&mut big).unwrap();
+        assert_eq!(big.life_stage(), LifeStage::Senior);
+    }
+
+    #[test]
+    fn test_rejects_bad_overrides() {
+        let mut cat = convert("cat", 3.0).unwrap();
+        let bad = [
+            "[life_stages.cat]\nelder = 10.0",
+            "[life_stages.cat]\nbaby = 0.5",
+            "[life_stages.cat]\nsenior = 20.0",
+        ];
+        for toml in bad {
+            assert!(stages(toml).apply(&mut cat).is_err(), "{}", toml);
+        }
+    }
+}
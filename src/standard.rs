@@ -0,0 +1,170 @@
+//! Conversion standards for `--standard`: the built-in families of formulas plus
+//! custom ones from the config file, and the per-species defaults set there.
+//!
+//! ```toml
+//! [default_standards]
+//! cat = "aaha"
+//! dog = "epigenetic"    # every dog size class
+//!
+//! [standards.clinic.rabbit]
+//! model = "two_stage"
+//! early_years = 1.0
+//! early_rate = 16.0
+//! later_rate = 6.0
+//! ```
+
+use crate::config::Config;
+use crate::{AppError, Conversion};
+use animal_age::animal::{Animal, Formula, Standard};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// Where the formulas of a custom standard come from.
+const CUSTOM_SOURCE: &str = "custom formula from the config file";
+
+/// The standards that can be picked, and which one each species uses by default.
+#[derive(Default)]
+pub struct Standards {
+    /// Custom standards: formulas keyed by standard name, then by animal key.
+    custom: BTreeMap<String, BTreeMap<String, Formula>>,
+    /// Default standard names keyed by animal key, or `dog` for every dog.
+    defaults: BTreeMap<String, String>,
+}
+
+impl Standards {
+    pub fn from_config(config: &Config) -> Standards {
+        Standards {
+            custom: config.standards.clone(),
+            defaults: config.default_standards.clone(),
+        }
+    }
+
+    /// The standards from the config file.
+    pub fn load() -> Result<Standards, AppError> {
+        Ok(Standards::from_config(&Config::load()?))
+    }
+
+    /// Every standard's name: the built-in ones, then the custom ones.
+    fn names(&self) -> Vec<&str> {
+        Standard::ALL
+            .iter()
+            .map(|standard| standard.key())
+            .chain(self.custom.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// The formula standard `name` gives `animal`, and where it comes from.
+    fn formula(
+        &self,
+        name: &str,
+        animal: Animal,
+    ) -> Result<(Formula, Cow<'static, str>, Cow<'static, str>), AppError> {
+        let formula = if let Some(formulas) = self.custom.get(name) {
+            formulas
+                .get(animal.key())
+                .map(|&formula| (formula, Cow::Owned(name.to_string()), CUSTOM_SOURCE.into()))
+        } else if let Ok(standard) = name.parse::<Standard>() {
+            animal
+                .formula_for(standard)
+                .map(|formula| (formula, standard.key().into(), standard.source().into()))
+        } else {
+            return Err(AppError::InvalidInput(format!(
+                "unknown standard '{}'; choose one of {}",
+                name,
+                self.names().join(", ")
+            )));
+        };
+        formula.ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "the {} standard does not cover {}",
+                name,
+                animal.key()
+            ))
+        })
+    }
+
+    /// The standards with a formula for `animal`.
+    pub fn covering(&self, animal: Animal) -> Vec<&str> {
+        self.names()
+            .into_iter()
+            .filter(|name| self.formula(name, animal).is_ok())
+            .collect()
+    }
+
+    /// Switches `conversion` to the `requested` standard, or else to its
+    /// species' default from the config file. Without either it keeps the
+    /// classic formula.
+    pub fn apply(
+        &self,
+        requested: Option<&str>,
+        conversion: &mut Conversion,
+    ) -> Result<(), AppError> {
+        let animal = conversion.animal;
+        let default = || {
+            self.defaults
+                .get(animal.key())
+                .or_else(|| self.defaults.get("dog").filter(|_| animal.is_dog()))
+        };
+        let Some(name) = requested.or_else(|| default().map(String::as_str)) else {
+            return Ok(());
+        };
+        let (formula, standard, source) = self.formula(name, animal)?;
+        conversion.set_formula(formula, standard, source);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    fn standards() -> Standards {
+        let config: Config = toml::from_str(
+            r#"
+            [default_standards]
+            cat = "aaha"
+            dog = "epigenetic"
+
+            [standards.clinic.rabbit]
+            model = "linear"
+            offset = 0.0
+            rate = 10.0
+            "#,
+        )
+        .unwrap();
+        Standards::from_config(&config)
+    }
+
+    #[test]
+    fn test_defaults_per_species() {
+        let standards = standards();
+        let mut cat = convert("cat", 2.0).unwrap();
+        standards.apply(None, &mut cat).unwrap();
+        assert_eq!(cat.human_age, 24.0);
+        assert_eq!(cat.standard, "aaha");
+
+        let mut dog = convert("big_dog", 1.0).unwrap();
+        standards.apply(None, &mut dog).unwrap();
+        assert_eq!(dog.human_age, 31.0);
+
+        // An explicit standard wins over the default.
+        let mut cat = convert("cat", 2.0).unwrap();
+        standards.apply(Some("classic"), &mut cat).unwrap();
+        assert_eq!(cat.human_age, 25.0);
+    }
+
+    #[test]
+    fn test_custom_standard() {
+        let standards = standards();
+        let mut rabbit = convert("rabbit", 3.0).unwrap();
+        standards.apply(Some("clinic"), &mut rabbit).unwrap();
+        assert_eq!(rabbit.human_age, 30.0);
+        assert_eq!(rabbit.formula_source, CUSTOM_SOURCE);
+        assert_eq!(standards.covering(Animal::Rabbit), ["classic", "clinic"]);
+
+        let mut hamster = convert("hamster", 1.0).unwrap();
+        assert!(standards.apply(Some("clinic"), &mut hamster).is_err());
+        assert!(standards.apply(Some("vedic"), &mut hamster).is_err());
+    }
+}
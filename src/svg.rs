@@ -0,0 +1,127 @@
+//! Scalable vector rendering of the lifespan bars, for `--format svg`.
+
+use crate::output::escape_markup;
+use crate::Conversion;
+use std::io::{self, Write};
+
+const WIDTH: f32 = 640.0;
+const MARGIN: f32 = 20.0;
+const LABEL_WIDTH: f32 = 150.0;
+const BAR_WIDTH: f32 = 380.0;
+const BAR_HEIGHT: f32 = 18.0;
+const ROW_HEIGHT: f32 = 28.0;
+/// A title line plus the human and animal bars.
+const BLOCK_HEIGHT: f32 = 30.0 + 2.0 * ROW_HEIGHT + 16.0;
+
+/// Fill colors matching the terminal bars' thresholds.
+fn bar_color(progress: f32) -> &'static str {
+    if progress >= 0.8 {
+        "#c0392b"
+    } else if progress >= 0.6 {
+        "#d4a017"
+    } else {
+        "#2aa198"
+    }
+}
+
+fn write_bar(out: &mut dyn Write, y: f32, label: &str, progress: f32) -> io::Result<()> {
+    let filled = BAR_WIDTH * progress.clamp(0.0, 1.0);
+    let bar_x = MARGIN + LABEL_WIDTH;
+    writeln!(
+        out,
+        r#"  <text x="{}" y="{}" class="label">{}</text>"#,
+        MARGIN,
+        y + BAR_HEIGHT - 4.0,
+        escape_markup(label)
+    )?;
+    writeln!(
+        out,
+        r##"  <rect x="{}" y="{}" width="{}" height="{}" rx="4" fill="#eee"/>"##,
+        bar_x, y, BAR_WIDTH, BAR_HEIGHT
+    )?;
+    writeln!(
+        out,
+        r#"  <rect x="{}" y="{}" width="{:.1}" height="{}" rx="4" fill="{}"/>"#,
+        bar_x,
+        y,
+        filled,
+        BAR_HEIGHT,
+        bar_color(progress)
+    )?;
+    writeln!(
+        out,
+        r#"  <text x="{}" y="{}" class="pct">{:.0}%</text>"#,
+        bar_x + BAR_WIDTH + 8.0,
+        y + BAR_HEIGHT - 4.0,
+        progress * 100.0
+    )
+}
+
+/// Writes one SVG image with a titled pair of bars (human and animal) per conversion.
+pub fn write_chart(conversions: &[Conversion], out: &mut dyn Write) -> io::Result<()> {
+    let height = 2.0 * MARGIN + BLOCK_HEIGHT * conversions.len() as f32;
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        WIDTH, height
+    )?;
+    writeln!(
+        out,
+        "  <style>text {{ font-family: system-ui, sans-serif; font-size: 13px; fill: #222; }} \
+         .title {{ font-size: 15px; font-weight: bold; }} .pct {{ fill: #555; }}</style>"
+    )?;
+    writeln!(out, r#"  <rect width="100%" height="100%" fill="white"/>"#)?;
+
+    for (idx, conversion) in conversions.iter().enumerate() {
+        let top = MARGIN + BLOCK_HEIGHT * idx as f32;
+        let title = match &conversion.name {
+            Some(name) => format!("{} ({})", name, conversion.label),
+            None => conversion.label.clone(),
+        };
+        writeln!(
+            out,
+            r#"  <text x="{}" y="{}" class="title">{}: {} years ≈ {:.1} human years</text>"#,
+            MARGIN,
+            top + 16.0,
+            escape_markup(&title),
+            conversion.age,
+            conversion.human_age
+        )?;
+        write_bar(
+            out,
+            top + 30.0,
+            "Human",
+            conversion.human_age / conversion.human_max,
+        )?;
+        write_bar(
+            out,
+            top + 30.0 + ROW_HEIGHT,
+            conversion.key(),
+            conversion.animal_progress(),
+        )?;
+    }
+
+    writeln!(out, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    #[test]
+    fn test_chart_has_two_bars_per_animal() {
+        let pets = [
+            convert("cat", 3.0).unwrap(),
+            convert("horse", 40.0).unwrap(),
+        ];
+        let mut svg = Vec::new();
+        write_chart(&pets, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(r##"fill="#eee""##).count(), 4);
+        // Ages past the lifespan are drawn as a full bar, not an overflowing one.
+        assert!(svg.contains(&format!(r#"width="{:.1}""#, BAR_WIDTH)));
+    }
+}
@@ -0,0 +1,365 @@
+//! Color themes for the terminal output: the bar colors at each threshold level,
+//! how labels are styled, and how percentages are written. Built-in themes are
+//! picked with `--theme`; custom ones are defined under `[themes.NAME]` in the
+//! config file.
+
+use crate::bar::Level;
+use crate::AppError;
+use animal_age::animal::Animal;
+use comfy_table::{Attribute, Color};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The eight standard terminal colors, in ANSI order.
+const NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Rough 24-bit equivalents of the standard colors, for gradients.
+const ANSI_RGB: [[u8; 3]; 8] = [
+    [0, 0, 0],
+    [220, 40, 40],
+    [40, 180, 60],
+    [230, 190, 0],
+    [40, 100, 220],
+    [190, 60, 190],
+    [0, 190, 190],
+    [220, 220, 220],
+];
+
+/// A foreground color: `none`, a standard color name, or `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Paint {
+    Plain,
+    /// Index into the standard colors.
+    Ansi(u8),
+    Rgb([u8; 3]),
+}
+
+impl Paint {
+    /// The escape sequence that switches to this color, if it is one.
+    pub fn escape(self) -> Option<String> {
+        match self {
+            Paint::Plain => None,
+            Paint::Ansi(index) => Some(format!("\x1b[{}m", 30 + index)),
+            Paint::Rgb([r, g, b]) => Some(format!("\x1b[38;2;{};{};{}m", r, g, b)),
+        }
+    }
+
+    /// The same color for a comfy-table cell.
+    pub fn table_color(self) -> Option<Color> {
+        match self {
+            Paint::Plain => None,
+            Paint::Ansi(index) => Some(
+                [
+                    Color::Black,
+                    Color::Red,
+                    Color::Green,
+                    Color::Yellow,
+                    Color::Blue,
+                    Color::Magenta,
+                    Color::Cyan,
+                    Color::White,
+                ][index as usize],
+            ),
+            Paint::Rgb([r, g, b]) => Some(Color::Rgb { r, g, b }),
+        }
+    }
+
+    fn rgb(self) -> Option<[u8; 3]> {
+        match self {
+            Paint::Plain => None,
+            Paint::Ansi(index) => Some(ANSI_RGB[index as usize]),
+            Paint::Rgb(rgb) => Some(rgb),
+        }
+    }
+}
+
+impl FromStr for Paint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s == "none" {
+            return Ok(Paint::Plain);
+        }
+        if let Some(index) = NAMES.iter().position(|&name| name == s) {
+            return Ok(Paint::Ansi(index as u8));
+        }
+        let hex = s
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(hex) => Ok(Paint::Rgb([(hex >> 16) as u8, (hex >> 8) as u8, hex as u8])),
+            None => Err(format!(
+                "unknown color '{}'; use none, {}, or #rrggbb",
+                s,
+                NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Paint {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Paint::Plain => write!(f, "none"),
+            Paint::Ansi(index) => write!(f, "{}", NAMES[*index as usize]),
+            Paint::Rgb([r, g, b]) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
+
+impl From<Paint> for String {
+    fn from(paint: Paint) -> Self {
+        paint.to_string()
+    }
+}
+
+/// The color of `animal` wherever several animals are shown together: its bars,
+/// its curve, and its legend entry. Dogs get shades of orange by size.
+pub fn species_paint(animal: Animal) -> Paint {
+    Paint::Rgb(match animal {
+        Animal::SmallDog => [255, 187, 120],
+        Animal::MediumDog => [255, 127, 14],
+        Animal::BigDog => [200, 82, 0],
+        Animal::GiantDog => [140, 50, 0],
+        Animal::Cat => [31, 119, 180],
+        Animal::Horse => [140, 86, 75],
+        Animal::Pig => [227, 119, 194],
+        Animal::Parakeet => [44, 160, 44],
+        Animal::Snake => [188, 189, 34],
+        Animal::Goldfish => [255, 215, 0],
+        Animal::Rabbit => [23, 190, 207],
+        Animal::Hamster => [148, 103, 189],
+        Animal::GuineaPig => [196, 156, 148],
+        Animal::Ferret => [214, 39, 40],
+        Animal::Turtle => [0, 128, 128],
+        Animal::Tortoise => [107, 142, 35],
+        Animal::Cow => [90, 90, 90],
+        Animal::Goat => [205, 133, 63],
+        Animal::Sheep => [220, 220, 220],
+        Animal::Chicken => [255, 99, 71],
+        Animal::Rat => [128, 128, 0],
+        Animal::Mouse => [176, 176, 176],
+        Animal::Gerbil => [222, 184, 135],
+        Animal::Chinchilla => [119, 136, 153],
+        Animal::Cockatiel => [240, 230, 140],
+        Animal::Canary => [255, 255, 0],
+        Animal::Macaw => [0, 0, 205],
+        Animal::BeardedDragon => [210, 105, 30],
+        Animal::LeopardGecko => [255, 165, 0],
+        Animal::Iguana => [50, 205, 50],
+        Animal::Koi => [255, 69, 0],
+        Animal::Betta => [138, 43, 226],
+        Animal::Pony => [188, 143, 143],
+        Animal::Donkey => [105, 105, 105],
+        Animal::Mule => [160, 82, 45],
+        Animal::Llama => [245, 222, 179],
+        Animal::Alpaca => [210, 180, 140],
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelStyle {
+    #[default]
+    Plain,
+    Bold,
+    Dim,
+    Italic,
+    Underline,
+}
+
+impl LabelStyle {
+    fn escape(self) -> Option<&'static str> {
+        match self {
+            LabelStyle::Plain => None,
+            LabelStyle::Bold => Some("\x1b[1m"),
+            LabelStyle::Dim => Some("\x1b[2m"),
+            LabelStyle::Italic => Some("\x1b[3m"),
+            LabelStyle::Underline => Some("\x1b[4m"),
+        }
+    }
+
+    /// The same style for a comfy-table cell.
+    pub fn attribute(self) -> Option<Attribute> {
+        match self {
+            LabelStyle::Plain => None,
+            LabelStyle::Bold => Some(Attribute::Bold),
+            LabelStyle::Dim => Some(Attribute::Dim),
+            LabelStyle::Italic => Some(Attribute::Italic),
+            LabelStyle::Underline => Some(Attribute::Underlined),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Bar color below the yellow threshold.
+    pub low: Paint,
+    /// Bar color between the thresholds.
+    pub mid: Paint,
+    /// Bar color past the red threshold.
+    pub high: Paint,
+    /// Style of the animal labels next to bars.
+    pub label: LabelStyle,
+    /// Decimal places of the percentages next to bars.
+    pub percent_decimals: u8,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            low: Paint::Ansi(6),
+            mid: Paint::Ansi(3),
+            high: Paint::Ansi(1),
+            label: LabelStyle::Plain,
+            percent_decimals: 0,
+        }
+    }
+}
+
+/// Names of the built-in themes, for `--theme` and its error message.
+pub const BUILT_IN: [&str; 5] = ["default", "pastel", "monochrome", "solarized", "colorblind"];
+
+impl Theme {
+    fn built_in(name: &str) -> Option<Theme> {
+        let theme = match name {
+            "default" => Theme::default(),
+            "pastel" => Theme {
+                low: Paint::Rgb([137, 207, 240]),
+                mid: Paint::Rgb([253, 253, 150]),
+                high: Paint::Rgb([255, 160, 160]),
+                label: LabelStyle::Italic,
+                percent_decimals: 0,
+            },
+            "monochrome" => Theme {
+                low: Paint::Plain,
+                mid: Paint::Plain,
+                high: Paint::Plain,
+                label: LabelStyle::Bold,
+                percent_decimals: 1,
+            },
+            "solarized" => Theme {
+                low: Paint::Rgb([42, 161, 152]),
+                mid: Paint::Rgb([181, 137, 0]),
+                high: Paint::Rgb([220, 50, 47]),
+                label: LabelStyle::Bold,
+                percent_decimals: 0,
+            },
+            // Okabe-Ito blue, orange, and vermillion, which stay apart for
+            // deuteranopes and protanopes.
+            "colorblind" => Theme {
+                low: Paint::Rgb([0, 114, 178]),
+                mid: Paint::Rgb([230, 159, 0]),
+                high: Paint::Rgb([213, 94, 0]),
+                label: LabelStyle::Plain,
+                percent_decimals: 0,
+            },
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// The theme called `name`: one from the config file's `[themes]`, else a built-in.
+    pub fn named(name: &str, custom: &BTreeMap<String, Theme>) -> Result<Theme, AppError> {
+        custom
+            .get(name)
+            .copied()
+            .or_else(|| Theme::built_in(name))
+            .ok_or_else(|| {
+                let known: Vec<&str> = BUILT_IN
+                    .into_iter()
+                    .chain(custom.keys().map(String::as_str))
+                    .collect();
+                AppError::Config(format!(
+                    "unknown theme '{}'; choose one of {}",
+                    name,
+                    known.join(", ")
+                ))
+            })
+    }
+
+    pub fn paint(&self, level: Level) -> Paint {
+        match level {
+            Level::Low => self.low,
+            Level::Mid => self.mid,
+            Level::High => self.high,
+        }
+    }
+
+    /// Gradient stops for truecolor bars, unless the theme leaves a level uncolored.
+    pub fn gradient(&self) -> Option<[[u8; 3]; 3]> {
+        Some([self.low.rgb()?, self.mid.rgb()?, self.high.rgb()?])
+    }
+
+    /// `progress` as a right-aligned percentage.
+    pub fn percent(&self, progress: f32) -> String {
+        let decimals = self.percent_decimals as usize;
+        let width = if decimals > 0 { 4 + decimals } else { 3 };
+        format!("{:>width$.decimals$}%", progress * 100.0)
+    }
+
+    /// `label` with the theme's label style, when color is on.
+    pub fn label(&self, label: &str, color: bool) -> String {
+        match self.label.escape().filter(|_| color) {
+            Some(style) => format!("{}{}\x1b[0m", style, label),
+            None => label.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_species_colors_are_distinct() {
+        let mut paints: Vec<String> = Animal::ALL
+            .iter()
+            .map(|&animal| species_paint(animal).to_string())
+            .collect();
+        paints.sort();
+        paints.dedup();
+        assert_eq!(paints.len(), Animal::ALL.len());
+    }
+
+    #[test]
+    fn test_custom_theme_from_config() {
+        let config: BTreeMap<String, Theme> = toml::from_str(
+            r##"
+            [mine]
+            low = "#268BD2"
+            high = "magenta"
+            label = "underline"
+            percent_decimals = 2
+            "##,
+        )
+        .unwrap();
+        let mine = Theme::named("mine", &config).unwrap();
+        assert_eq!(mine.low, Paint::Rgb([0x26, 0x8b, 0xd2]));
+        assert_eq!(mine.mid, Theme::default().mid);
+        assert_eq!(mine.paint(Level::High).escape().unwrap(), "\x1b[35m");
+        assert_eq!(mine.percent(0.5), " 50.00%");
+        assert_eq!(
+            Theme::named("pastel", &config).unwrap().label,
+            LabelStyle::Italic
+        );
+        assert!(Theme::named("neon", &config).is_err());
+        assert!("chartreuse".parse::<Paint>().is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Column bar charts for `--vertical`: one upright bar per animal, side by side,
+//! with its label and percentage underneath.
+
+use crate::bar::{BarStyle, Bars};
+use crate::theme::Paint;
+use std::io::{self, Write};
+use unicode_width::UnicodeWidthStr;
+
+/// Chart height in rows, from 0 to 100%.
+const COLUMN_HEIGHT: usize = 10;
+/// Characters across each bar.
+const COLUMN_THICKNESS: usize = 3;
+/// Partial cells of the `blocks` style, from one to seven eighths filled.
+const LOWER_EIGHTHS: [char; 7] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇'];
+
+/// One bar of the chart: its label and how full it is (a fraction, not a
+/// percentage; anything past 1 draws a full bar).
+pub struct Column {
+    pub label: String,
+    pub progress: f32,
+    /// The bar's color, instead of the one for its threshold level.
+    pub paint: Option<Paint>,
+}
+
+/// The cell of a column at `row`, counted from the bottom.
+fn cell(progress: f32, row: usize, bars: &Bars) -> char {
+    let progress = progress.clamp(0.0, 1.0);
+    match bars.style {
+        BarStyle::Ascii => {
+            let filled = (progress * COLUMN_HEIGHT as f32) as usize;
+            if row < filled {
+                bars.fill.unwrap_or('#')
+            } else {
+                ' '
+            }
+        }
+        BarStyle::Blocks => {
+            let eighths = (progress * COLUMN_HEIGHT as f32 * 8.0) as usize;
+            match eighths.saturating_sub(row * 8) {
+                0 => ' ',
+                partial @ 1..=7 => LOWER_EIGHTHS[partial - 1],
+                _ => bars.fill.unwrap_or('█'),
+            }
+        }
+    }
+}
+
+/// `text` centered in `width` columns.
+fn center(text: &str, width: usize) -> String {
+    let space = width.saturating_sub(text.width());
+    format!(
+        "{}{}{}",
+        " ".repeat(space / 2),
+        text,
+        " ".repeat(space - space / 2)
+    )
+}
+
+/// Writes the columns side by side above a 0% baseline, each in its own color or
+/// else colored by its threshold level, when color is on.
+pub fn write_columns(
+    columns: &[Column],
+    bars: &Bars,
+    color: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|column| column.label.width().max(5))
+        .collect();
+    for row in (0..COLUMN_HEIGHT).rev() {
+        let axis = match row {
+            _ if row == COLUMN_HEIGHT - 1 => "100%",
+            _ if row == COLUMN_HEIGHT / 2 - 1 => "50%",
+            _ => "",
+        };
+        let mut line = format!("{:>5} |", axis);
+        for (column, &width) in columns.iter().zip(&widths) {
+            let c = cell(column.progress, row, bars);
+            let bar = c.to_string().repeat(COLUMN_THICKNESS);
+            let escape = column
+                .paint
+                .unwrap_or_else(|| bars.theme.paint(bars.thresholds.level(column.progress)))
+                .escape()
+                .filter(|_| color && c != ' ');
+            let bar = match escape {
+                Some(escape) => format!("{}{}{}", escape, bar, crate::color::RESET),
+                None => bar,
+            };
+            let space = width - COLUMN_THICKNESS;
+            line.push_str(&format!(
+                " {}{}{}",
+                " ".repeat(space / 2),
+                bar,
+                " ".repeat(space - space / 2)
+            ));
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    let baseline: usize = widths.iter().map(|width| width + 1).sum();
+    writeln!(out, "{:>5} +{}", "0%", "-".repeat(baseline + 1))?;
+    for text in [
+        columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, &width)| center(&column.label, width))
+            .collect::<Vec<_>>(),
+        columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, &width)| center(bars.theme.percent(column.progress).trim(), width))
+            .collect(),
+    ] {
+        writeln!(out, "{:5}   {}", "", text.join(" ").trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_stand_side_by_side() {
+        let columns = [
+            Column {
+                label: "cat".to_string(),
+                progress: 0.5,
+                paint: None,
+            },
+            Column {
+                label: "hamster".to_string(),
+                progress: 1.2,
+                paint: None,
+            },
+        ];
+        let mut out = Vec::new();
+        write_columns(&columns, &Bars::default(), false, &mut out).unwrap();
+        let chart = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), COLUMN_HEIGHT + 3);
+        // Only the hamster reaches the top; the cat fills the bottom half.
+        assert_eq!(lines[0], " 100% |         ###");
+        assert_eq!(lines[5], "  50% |  ###    ###");
+        assert_eq!(lines[10], "   0% +---------------");
+        assert_eq!(lines[11], "         cat  hamster");
+        assert_eq!(lines[12], "         50%   120%");
+    }
+
+    #[test]
+    fn test_wide_labels_are_centered_by_columns() {
+        assert_eq!(center("猫", 5), " 猫  ");
+        assert_eq!(center("🐹 hamster", 10), "🐹 hamster");
+        assert_eq!(center("cat", 5), " cat ");
+    }
+
+    #[test]
+    fn test_blocks_fill_by_eighths() {
+        let blocks = Bars {
+            style: BarStyle::Blocks,
+            ..Bars::default()
+        };
+        assert_eq!(cell(0.05, 0, &blocks), '▄');
+        assert_eq!(cell(0.05, 1, &blocks), ' ');
+        assert_eq!(cell(0.5, 4, &blocks), '█');
+    }
+}
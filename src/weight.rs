@@ -0,0 +1,94 @@
+//! Body weights, used to pick a dog's size class.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Kilograms per avoirdupois pound.
+pub const KG_PER_LB: f32 = 0.453_592_37;
+
+/// Heaviest weight accepted, in kilograms; comfortably above any dog breed.
+pub const MAX_WEIGHT_KG: f32 = 200.0;
+
+/// A body weight, stored in kilograms.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Weight {
+    kg: f32,
+}
+
+/// Error returned when a string is not a valid weight such as `28kg` or `60 lb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidWeight;
+
+impl fmt::Display for InvalidWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid weight, expected a positive number up to {} kg with an optional kg or lb unit",
+            MAX_WEIGHT_KG
+        )
+    }
+}
+
+impl std::error::Error for InvalidWeight {}
+
+impl Weight {
+    pub fn from_kg(kg: f32) -> Result<Weight, InvalidWeight> {
+        if kg.is_finite() && kg > 0.0 && kg <= MAX_WEIGHT_KG {
+            Ok(Weight { kg })
+        } else {
+            Err(InvalidWeight)
+        }
+    }
+
+    pub fn kg(&self) -> f32 {
+        self.kg
+    }
+}
+
+impl FromStr for Weight {
+    type Err = InvalidWeight;
+
+    /// Parses `28`, `28kg`, `28.5 kg`, `60lb`, or `60 lbs`; a bare number is kilograms.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| c.is_alphabetic()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+
+        let number = number.trim();
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+            return Err(InvalidWeight);
+        }
+        let value: f32 = number.parse().map_err(|_| InvalidWeight)?;
+
+        let kg = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "kg" | "kgs" | "kilo" | "kilos" => value,
+            "lb" | "lbs" | "pound" | "pounds" => value * KG_PER_LB,
+            _ => return Err(InvalidWeight),
+        };
+        Weight::from_kg(kg)
+    }
+}
+
+impl fmt::Display for Weight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} kg", (self.kg * 10.0).round() / 10.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weight() {
+        assert_eq!("28kg".parse::<Weight>().unwrap().kg(), 28.0);
+        assert_eq!("28.5 KG".parse::<Weight>().unwrap().kg(), 28.5);
+        assert_eq!("12".parse::<Weight>().unwrap().kg(), 12.0);
+        assert!(("60 lbs".parse::<Weight>().unwrap().kg() - 27.215_54).abs() < 1e-4);
+        assert!("0kg".parse::<Weight>().is_err());
+        assert!("-5kg".parse::<Weight>().is_err());
+        assert!("28 stone".parse::<Weight>().is_err());
+        assert!("inf".parse::<Weight>().is_err());
+        assert!("900kg".parse::<Weight>().is_err());
+    }
+}
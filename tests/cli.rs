@@ -0,0 +1,270 @@
+//! End-to-end tests of the `animal-age` binary: exit codes, parsing, and output
+//! that scripts depend on.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use serde_json::Value;
+
+/// The binary, isolated from the user's config file and without color.
+fn animal_age() -> Command {
+    let mut cmd = assert_cmd::cargo_bin_cmd!("animal-age");
+    cmd.env("ANIMAL_AGE_CONFIG", "/nonexistent/animal-age/config.toml")
+        .env("NO_COLOR", "1");
+    cmd
+}
+
+fn json(output: &[u8]) -> Value {
+    serde_json::from_slice(output).expect("output is valid JSON")
+}
+
+#[test]
+fn test_converts_one_animal() {
+    animal_age()
+        .args(["-t", "cat", "-a", "3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "3 years old cat ≈ 29.0 human years",
+        ))
+        .stderr("");
+}
+
+#[test]
+fn test_list() {
+    let assert = animal_age().arg("--list").assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    for key in ["small_dog", "giant_dog", "cat", "hamster"] {
+        assert!(stdout.contains(key), "--list is missing {}", key);
+    }
+
+    let assert = animal_age().args(["--list", "--json"]).assert().success();
+    let animals = json(&assert.get_output().stdout)["animals"].clone();
+    assert_eq!(animals[0]["key"], "small_dog");
+    assert_eq!(animals[0]["formula"], "two_stage");
+
+    animal_age()
+        .args(["--list", "--filter", "rodents", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hamster,"))
+        .stdout(predicate::str::contains("cat,").not());
+}
+
+#[test]
+fn test_compares_several_animals() {
+    animal_age()
+        .args(["-t", "cat,small_dog", "-a", "3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 years old in human years"))
+        .stdout(predicate::str::contains("cat"))
+        .stdout(predicate::str::contains("small_dog"));
+
+    let assert = animal_age()
+        .args(["-t", "Cats, horse", "-a", "3", "--json"])
+        .assert()
+        .success();
+    let results = json(&assert.get_output().stdout)["results"].clone();
+    let animals: Vec<&str> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["animal"].as_str().unwrap())
+        .collect();
+    assert_eq!(animals, ["cat", "horse"]);
+}
+
+#[test]
+fn test_all_animals() {
+    let assert = animal_age()
+        .args(["-t", "all", "-a", "3", "--json"])
+        .assert()
+        .success();
+    let results = json(&assert.get_output().stdout)["results"].clone();
+    let results = results.as_array().unwrap();
+    assert!(results.len() > 30);
+    assert!(results.iter().any(|result| result["animal"] == "tortoise"));
+}
+
+#[test]
+fn test_unknown_animal_suggests_one() {
+    animal_age()
+        .args(["-t", "catt", "-a", "3"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "Did you mean 'cat', 'rat', or 'cavy'?",
+        ))
+        .stderr(predicate::str::contains("--list"));
+    animal_age()
+        .args(["-t", "dog", "-a", "3"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Did you mean 'small_dog', 'medium_dog', or 'big_dog'?",
+        ));
+    animal_age()
+        .args(["-t", "doggo", "-a", "3", "--strict", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warnings").not())
+        .stderr(predicate::str::starts_with(
+            "Note: 'doggo' is taken as medium_dog",
+        ));
+    animal_age()
+        .args(["-t", "gat", "-a", "3"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Did you mean 'cat', 'goat', or 'rat'?",
+        ));
+    animal_age()
+        .args(["-t", "zebra", "-a", "3"])
+        .assert()
+        .code(1)
+        .stderr(
+            predicate::str::contains("Unknown animal type: zebra")
+                .and(predicate::str::contains("Did you mean").not()),
+        );
+}
+
+#[test]
+fn test_prefixes() {
+    animal_age()
+        .args(["-t", "ham", "-a", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("1 years old hamster"));
+    animal_age()
+        .args(["-t", "go", "-a", "1"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Ambiguous animal type: go could be goldfish or goat.",
+        ));
+}
+
+#[test]
+fn test_exit_codes() {
+    animal_age().args(["-t", "cat"]).assert().code(1);
+    // Malformed arguments are usage errors.
+    for age in ["-1", "300", "three"] {
+        animal_age().args(["-t", "cat", "-a", age]).assert().code(2);
+    }
+    // --check: 0 when true, 1 when false, and 2 for anything else.
+    let check = |expression: &str| {
+        animal_age()
+            .args(["-t", "cat", "-a", "12", "--check", expression])
+            .assert()
+    };
+    check("human_age >= 65").code(0).stdout("");
+    check("human_age >= 70").code(1);
+    check("human_age >>= 70").code(2);
+}
+
+#[test]
+fn test_json_output() {
+    let assert = animal_age()
+        .args(["-t", "cat", "-a", "3", "--json"])
+        .assert()
+        .success();
+    let report = json(&assert.get_output().stdout);
+    assert_eq!(report["results"].as_array().unwrap().len(), 1);
+    let output = &report["results"][0];
+    assert_eq!(output["animal"], "cat");
+    assert_eq!(output["human_age"], 29.0);
+    assert_eq!(output["life_stage"], "adult");
+    assert_eq!(output["formula"]["name"], "two_stage");
+    assert!(output.get("warnings").is_none());
+
+    // Warnings ride along in the JSON instead of going to stderr.
+    let assert = animal_age()
+        .args(["-t", "hamster", "-a", "5", "--json"])
+        .assert()
+        .success()
+        .stderr("");
+    let output = json(&assert.get_output().stdout);
+    assert_eq!(
+        output["results"][0]["warnings"].as_array().unwrap().len(),
+        1
+    );
+
+    // Batch input gets the same document.
+    let assert = animal_age()
+        .args(["--stdin", "--json"])
+        .write_stdin("cat, 3\nhorse, 10\n")
+        .assert()
+        .success();
+    let report = json(&assert.get_output().stdout);
+    assert_eq!(report["results"][1]["animal"], "horse");
+}
+
+#[test]
+fn test_ndjson_batch_from_stdin() {
+    let assert = animal_age()
+        .args(["--stdin", "--format", "ndjson"])
+        .write_stdin("cat, 3\n# a comment\nhorse, 10\n")
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let lines: Vec<Value> = stdout.lines().map(|line| json(line.as_bytes())).collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["animal"], "horse");
+}
+
+#[test]
+fn test_schema_is_json() {
+    let assert = animal_age().arg("schema").assert().success();
+    let schema = json(&assert.get_output().stdout);
+    assert!(schema["$defs"].is_object() || schema["properties"].is_object());
+}
+
+#[test]
+fn test_calibrates_an_exotic_species() {
+    let dir = std::env::temp_dir().join(format!("animal-age-calibrate-{}", std::process::id()));
+    let config = dir.join("config.toml");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&config, "# kept\nhistory = false\n").unwrap();
+    animal_age()
+        .env("ANIMAL_AGE_CONFIG", &config)
+        .args(["calibrate", "-t", "axolotl", "--points", "1=10,2=20,5=40"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "'calibrated' standard for axolotl",
+        ));
+    let saved = std::fs::read_to_string(&config).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(saved.starts_with("# kept\nhistory = false\n"));
+    assert!(saved.contains("[standards.calibrated.axolotl]\nmodel = \"two_stage\""));
+}
+
+#[test]
+fn test_strict_checks_ages_from_human_years() {
+    animal_age()
+        .args(["-t", "hamster", "--from-human", "150", "--strict"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("exceeds typical hamster lifespan"));
+}
+
+#[test]
+fn test_rejects_a_falling_custom_standard() {
+    let dir = std::env::temp_dir().join(format!("animal-age-standard-{}", std::process::id()));
+    let config = dir.join("config.toml");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        &config,
+        "[standards.clinic.rabbit]\nmodel = \"linear\"\noffset = 0.0\nrate = -5.0\n",
+    )
+    .unwrap();
+    let assert = animal_age()
+        .env("ANIMAL_AGE_CONFIG", &config)
+        .args(["-t", "rabbit", "-a", "3", "--standard", "clinic"])
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert.failure().stderr(predicate::str::contains(
+        "standards.clinic.rabbit: rate must be above zero",
+    ));
+}